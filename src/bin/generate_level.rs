@@ -0,0 +1,88 @@
+extern crate ghgj18;
+extern crate log;
+
+use std::{
+  env,
+  io,
+  process,
+  str::FromStr,
+};
+
+use log::LevelFilter;
+
+use ghgj18::create_logger;
+use ghgj18::generation::{
+  Ability,
+  LevelParams,
+  generate_level,
+  level_to_ron,
+};
+
+const USAGE: &str = "Usage: generate_level [--seed N] [--count N] [--length N] [--gaps N] [--hazards 0-1] [--abilities lift,change_direction,ram]
+
+Prints the generated levels in the resources/config.ron format, ready to paste into the levels list.
+--count generates that many levels with consecutive seeds.";
+
+fn main() {
+  create_logger(LevelFilter::Info, io::stderr());
+
+  let (params, count) = match parse_args(env::args().skip(1).collect()) {
+    Ok(parsed) => parsed,
+    Err(e) => {
+      eprintln!("{}\n\n{}", e, USAGE);
+      process::exit(1);
+    },
+  };
+
+  for i in 0..count {
+    let params = LevelParams {
+      seed: params.seed.wrapping_add(i),
+      ..params.clone()
+    };
+    match level_to_ron(&generate_level(&params)) {
+      Ok(ron) => println!("{}, //seed {}", ron, params.seed),
+      Err(e) => {
+        eprintln!("Failed to serialize level: {}", e);
+        process::exit(1);
+      },
+    }
+  }
+}
+
+fn parse_args(args: Vec<String>) -> Result<(LevelParams, u64), String> {
+  let mut params = LevelParams::default();
+  let mut count = 1;
+
+  let mut args = args.into_iter();
+  while let Some(arg) = args.next() {
+    if arg == "--help" || arg == "-h" {
+      println!("{}", USAGE);
+      process::exit(0);
+    }
+
+    let value = args.next().ok_or(format!("Missing value for {}", arg))?;
+    match arg.as_str() {
+      "--seed" => params.seed = parse(&arg, &value)?,
+      "--count" => count = parse(&arg, &value)?,
+      "--length" => params.length = parse(&arg, &value)?,
+      "--gaps" => params.gaps = parse(&arg, &value)?,
+      "--hazards" => params.hazard_density = parse(&arg, &value)?,
+      "--abilities" => {
+        params.abilities = value
+          .split(',')
+          .filter(|s| !s.is_empty())
+          .map(|s| s.parse::<Ability>())
+          .collect::<Result<_, _>>()?;
+      },
+      _ => return Err(format!("Unknown argument {}", arg)),
+    }
+  }
+
+  Ok((params, count))
+}
+
+fn parse<T: FromStr>(arg: &str, value: &str) -> Result<T, String> {
+  value
+    .parse()
+    .map_err(|_| format!("Invalid value for {}: {}", arg, value))
+}
@@ -1,5 +1,5 @@
 ///Component represents the age of an entity
-#[derive(Default, Debug, Clone, Copy)]
+#[derive(Default, Debug, Clone, Copy, Deserialize, Serialize)]
 pub struct Age {
   pub seconds: f32,
   pub max: Option<f32>,
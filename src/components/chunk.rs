@@ -0,0 +1,6 @@
+///Component marking an entity as part of a generated chunk of endless terrain
+#[derive(Debug, Clone)]
+pub struct Chunk {
+  //Right hand edge of the chunk, once the camera is far enough past this the chunk is deleted
+  pub end_x: f32,
+}
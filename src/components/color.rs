@@ -1,7 +1,7 @@
 use std::convert::Into;
 
 ///Component represents the color of an entity
-#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
 pub struct Color {
   r: f32,
   g: f32,
@@ -4,7 +4,7 @@
 pub struct Family;
 
 ///Matriarch is the head of the family, there should only be one per family
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct Matriarch {
   pub age_when_promoted: f32,
 }
\ No newline at end of file
@@ -0,0 +1,3 @@
+///Component marking the ghost of the best run. It has no collider so nothing can touch it.
+#[derive(Debug, Clone, Default)]
+pub struct Ghost;
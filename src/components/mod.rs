@@ -22,6 +22,9 @@ mod deadly_area;
 mod age;
 mod launch_area;
 mod constant_velocity;
+mod chunk;
+mod ghost;
+mod sprite_animation;
 
 pub use self::basic_velocity::*;
 pub use self::physics::*;
@@ -36,6 +39,9 @@ pub use self::deadly_area::*;
 pub use self::age::*;
 pub use self::launch_area::*;
 pub use self::constant_velocity::*;
+pub use self::chunk::*;
+pub use self::ghost::*;
+pub use self::sprite_animation::*;
 
 //TODO: I've just used BTreeStorage for all of these as the specs book suggests it's ok as a general default.
 //  Think about using more appropriate storages at some point.
@@ -97,4 +103,16 @@ impl Component for LaunchArea {
 
 impl Component for ConstantVelocity {
   type Storage = BTreeStorage<Self>;
-}
\ No newline at end of file
+}
+
+impl Component for Chunk {
+  type Storage = BTreeStorage<Self>;
+}
+
+impl Component for Ghost {
+  type Storage = NullStorage<Self>;
+}
+
+impl Component for SpriteAnimation {
+  type Storage = BTreeStorage<Self>;
+}
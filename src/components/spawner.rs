@@ -6,7 +6,7 @@ pub struct SpawnerParams {
   pub frequency: f32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Spawner {
   pub spawn_size: Vector2<f32>,
   pub spawn_max: u32,
@@ -14,6 +14,10 @@ pub struct Spawner {
   pub spawn_count: u32,
   pub elapsed: f32,
   pub exodus: bool,
+  //Doesn't count down or spawn until it's woken up (endless chunks are generated well ahead of the action)
+  #[serde(default)]
+  pub dormant: bool,
+  #[serde(skip)]
   _private: (),
 }
 
@@ -26,6 +30,7 @@ impl Spawner {
       spawn_count: 0,
       elapsed: 0.0,
       exodus: false,
+      dormant: false,
       _private: (),
     }
   }
@@ -0,0 +1,24 @@
+///Component that steps the entity's SpriteRender through one of the animations in SpritesConfig
+#[derive(Debug, Clone)]
+pub struct SpriteAnimation {
+  pub animation: String,
+  //Seconds since the animation started
+  pub elapsed: f32,
+}
+
+impl SpriteAnimation {
+  pub fn new(animation: &str) -> Self {
+    Self {
+      animation: animation.to_string(),
+      elapsed: 0.0,
+    }
+  }
+
+  ///Switches to a different animation from the start. Does nothing if it's already playing
+  pub fn play(&mut self, animation: &str) {
+    if self.animation != animation {
+      self.animation = animation.to_string();
+      self.elapsed = 0.0;
+    }
+  }
+}
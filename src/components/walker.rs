@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
 pub enum Direction {
   Right,
   Left,
@@ -20,7 +20,7 @@ impl Default for Direction {
 }
 
 ///Component that applies a force to an entity to cause it to move in one direction until it hits a wall
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Walker {
   //force is current set system wide in the Walker system... pub force: f32,
   pub direction: Direction,
@@ -36,7 +36,7 @@ impl Default for Walker {
 }
 
 ///Component that changes a walkers direction when their physics bodies overlap
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChangeDirection {
   pub direction: Direction,
 }
\ No newline at end of file
@@ -1,6 +1,18 @@
 use amethyst::core::cgmath::Vector3;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum ProjectionMode {
+  Perspective,
+  Orthographic,
+}
+
+impl Default for ProjectionMode {
+  fn default() -> Self {
+    ProjectionMode::Perspective
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct CameraConfig {
   //Multiplied by time to give a fraction of how much the target location contributes to the new
   // location of the camera.
@@ -14,6 +26,23 @@ pub struct CameraConfig {
   //Turned off if <= 0
   pub gridline_width: f32,
   pub final_position: Option<Vector3<f32>>,
+  #[serde(default)]
+  pub projection: ProjectionMode,
+  //Vertical field of view in degrees. Orthographic uses it too so the camera's z zooms it the same way
+  #[serde(default = "default_fov")]
+  pub fov: f32,
+}
+
+fn default_fov() -> f32 {
+  60.0
+}
+
+impl CameraConfig {
+  ///Half the width and height of the area visible on the z = 0 plane from a camera at z
+  pub fn visible_half_extents(&self, z: f32, aspect: f32) -> (f32, f32) {
+    let half_height = z * (self.fov.to_radians() * 0.5).tan();
+    (half_height * aspect, half_height)
+  }
 }
 
 impl Default for CameraConfig {
@@ -27,6 +56,8 @@ impl Default for CameraConfig {
       zoom_speed: 100.0,
       gridline_width: 1.0,
       final_position: None,
+      projection: ProjectionMode::default(),
+      fov: default_fov(),
     }
   }
 }
\ No newline at end of file
@@ -1,8 +1,11 @@
 use amethyst::core::cgmath::Vector3;
 
-use ::components::Color;
+use ::{
+  components::Color,
+  resources::SpawnStats,
+};
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Cuboid {
   pub size: Vector3<f32>,
   pub position: Vector3<f32>,
@@ -10,13 +13,13 @@ pub struct Cuboid {
   pub rotation: Option<f32>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct CuboidSet {
   pub list: Vec<Cuboid>,
   pub color: Option<Color>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct CameraOverrides {
   pub offset: Option<Vector3<f32>>,
   pub convergence_speed: Option<f32>,
@@ -25,14 +28,97 @@ pub struct CameraOverrides {
   pub final_position: Option<Option<Vector3<f32>>>,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+///Area of the level the camera is kept inside
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq)]
+pub struct Bounds {
+  pub left: f32,
+  pub bottom: f32,
+  pub right: f32,
+  pub top: f32,
+}
+
+impl Bounds {
+  fn union(self, other: Bounds) -> Bounds {
+    Bounds {
+      left: self.left.min(other.left),
+      bottom: self.bottom.min(other.bottom),
+      right: self.right.max(other.right),
+      top: self.top.max(other.top),
+    }
+  }
+}
+
+impl Cuboid {
+  ///The axis aligned box around the cuboid once it's rotated
+  pub fn bounds(&self) -> Bounds {
+    let rotation = self.rotation.unwrap_or(0.0);
+    let (sin, cos) = (rotation.sin().abs(), rotation.cos().abs());
+    let half_width = (self.size.x * cos + self.size.y * sin) * 0.5;
+    let half_height = (self.size.x * sin + self.size.y * cos) * 0.5;
+    Bounds {
+      left: self.position.x - half_width,
+      bottom: self.position.y - half_height,
+      right: self.position.x + half_width,
+      top: self.position.y + half_height,
+    }
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct SpawnOverides {
   pub freq: f32,
   pub max: u32,
   pub win_ratio: f32,
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+//Optional targets used to give completed runs a star rating
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
+pub struct ScoringConfig {
+  //Seconds
+  pub par_time: Option<f32>,
+  pub par_abilities: Option<u32>,
+  //Saved ratios (0-1) needed for each medal. Bronze defaults to the level's win ratio
+  pub bronze: Option<f32>,
+  pub silver: Option<f32>,
+  pub gold: Option<f32>,
+}
+
+impl ScoringConfig {
+  ///One star per medal reached plus one for each par beaten
+  pub fn stars(&self, stats: &SpawnStats, runtime: f32) -> u32 {
+    let saved_ratio = stats.saved_ratio();
+    let bronze = self.bronze.unwrap_or(stats.win_ratio);
+
+    let mut stars = 0;
+    if saved_ratio >= bronze {
+      stars += 1;
+    }
+    if self.silver.map_or(false, |silver| saved_ratio >= silver) {
+      stars += 1;
+    }
+    if self.gold.map_or(false, |gold| saved_ratio >= gold) {
+      stars += 1;
+    }
+    if self.par_time.map_or(false, |par_time| runtime <= par_time) {
+      stars += 1;
+    }
+    if self.par_abilities.map_or(false, |par_abilities| stats.abilities_used <= par_abilities) {
+      stars += 1;
+    }
+    stars
+  }
+
+  pub fn max_stars(&self) -> u32 {
+    1 + [
+      self.silver.is_some(),
+      self.gold.is_some(),
+      self.par_time.is_some(),
+      self.par_abilities.is_some(),
+    ].iter().filter(|s| **s).count() as u32
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct LevelConfig {
   pub name: Option<String>,
   pub description: Option<String>,
@@ -43,9 +129,32 @@ pub struct LevelConfig {
   pub blocks: Option<CuboidSet>,
   pub spawn_overrides: Option<SpawnOverides>,
   pub camera_overrides: Option<CameraOverrides>,
+  pub scoring: Option<ScoringConfig>,
+  //Replaces the music playlist while this level is loaded. Files relative to assets/sound
+  pub music: Option<Vec<String>>,
+  //Keeps the camera inside this area, the box around the walls if not set
+  pub bounds: Option<Bounds>,
+}
+
+impl LevelConfig {
+  ///Area the camera should stay inside, None if there are no bounds or walls to work it out from
+  pub fn camera_bounds(&self) -> Option<Bounds> {
+    self.bounds.or_else(|| {
+      self.walls
+        .as_ref()
+        .and_then(|walls| walls.list
+          .iter()
+          .map(|wall| wall.bounds())
+          .fold(None, |acc: Option<Bounds>, b| Some(acc.map_or(b, |acc| acc.union(b)))))
+    })
+  }
+
+  pub fn scoring(&self) -> ScoringConfig {
+    self.scoring.clone().unwrap_or_default()
+  }
 }
 
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct LevelsConfig {
   pub start_level: Option<usize>,
   pub levels: Vec<LevelConfig>,
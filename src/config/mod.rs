@@ -17,11 +17,28 @@ mod sprite;
 
 pub use self::pawn::PawnConfig;
 pub use self::physics::PhysicsConfig;
-pub use self::camera::CameraConfig;
+pub use self::camera::{
+  CameraConfig,
+  ProjectionMode,
+};
 pub use self::spawner::SpawnerConfig;
 pub use self::levels::*;
-pub use self::sound::SoundConfig;
-pub use self::sprite::SpritesConfig;
+pub use self::sound::{
+  SoundConfig,
+  MusicConfig,
+  PositionalConfig,
+  SoundEffectConfig,
+};
+pub use self::sprite::{
+  AnimationConfig,
+  AtlasConfig,
+  AtlasLayout,
+  GridConfig,
+  FrameConfig,
+  LoopMode,
+  SpriteConfig,
+  SpritesConfig,
+};
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct GameConfig {
@@ -50,6 +67,10 @@ impl Default for GameConfig {
   }
 }
 
+pub fn game_config_path() -> String {
+  format!("{}/resources/config.ron", application_root_dir())
+}
+
 pub fn load_game_config() -> Result<GameConfig, ConfigError> {
-  GameConfig::load_no_fallback(&format!("{}/resources/config.ron", application_root_dir()))
+  GameConfig::load_no_fallback(&game_config_path())
 }
\ No newline at end of file
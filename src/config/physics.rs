@@ -1,6 +1,6 @@
 use amethyst::core::cgmath::Vector2;
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct PhysicsConfig {
   pub gravity: f32,
   pub lift_width: f32,
@@ -19,6 +19,13 @@ pub struct PhysicsConfig {
   pub ram_life: f32,
   //How old does a matriarch have to be before it can accept commands
   pub matriarch_grace_period: f32,
+  //Speeds the game can be slowed down or sped up to. Anything that isn't above 0 is ignored
+  #[serde(default = "default_time_scales")]
+  pub time_scales: Vec<f32>,
+}
+
+fn default_time_scales() -> Vec<f32> {
+  vec![0.25, 0.5, 1.0, 2.0]
 }
 
 impl Default for PhysicsConfig {
@@ -39,6 +46,7 @@ impl Default for PhysicsConfig {
       ram_density: 100.0,
       ram_life: 0.5,
       matriarch_grace_period: 0.15,
+      time_scales: default_time_scales(),
     }
   }
 }
\ No newline at end of file
@@ -1,12 +1,87 @@
-#[derive(Debug, Deserialize, Serialize)]
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct SoundConfig {
+  //Master volume, everything is scaled by this
   pub volume: f32,
+  pub music_volume: f32,
+  pub sfx_volume: f32,
+  //How many sound effects can play at once, lower priority ones are cut off to make room
+  pub max_voices: usize,
+  pub positional: PositionalConfig,
+  pub music: MusicConfig,
+  //Sound effects by the id systems play them with
+  pub effects: BTreeMap<String, SoundEffectConfig>,
 }
 
 impl Default for SoundConfig {
   fn default() -> Self {
     Self {
       volume: 1.0,
+      music_volume: 1.0,
+      sfx_volume: 1.0,
+      max_voices: 16,
+      positional: Default::default(),
+      music: Default::default(),
+      effects: BTreeMap::new(),
     }
   }
-}
\ No newline at end of file
+}
+
+///One sound effect. Each time it's played one of the files is picked at random
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct SoundEffectConfig {
+  //Files relative to assets/sound, the format comes from the extension
+  pub files: Vec<String>,
+  pub volume: f32,
+  //Pitch is randomly moved up or down by up to this much, 0.1 is +-10%
+  #[serde(default)]
+  pub pitch_variation: f32,
+  //Seconds before it can be played again, anything sent sooner is dropped
+  #[serde(default)]
+  pub cooldown: f32,
+  //Higher priority sounds take voices from lower ones when max_voices is reached
+  #[serde(default)]
+  pub priority: u32,
+}
+
+///How sounds are panned and faded by their distance from the camera.
+///Distances are in pixels at the default zoom and scale with the camera's z.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct PositionalConfig {
+  //Horizontal distance at which a sound is only in one ear
+  pub pan_distance: f32,
+  //Distance at which a sound has faded to min_volume
+  pub falloff_distance: f32,
+  pub min_volume: f32,
+}
+
+impl Default for PositionalConfig {
+  fn default() -> Self {
+    Self {
+      pan_distance: 400.0,
+      falloff_distance: 800.0,
+      min_volume: 0.1,
+    }
+  }
+}
+
+///The playlist used everywhere a level doesn't have its own music
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct MusicConfig {
+  //Files relative to assets/sound
+  pub tracks: Vec<String>,
+  pub shuffle: bool,
+  //Start the playlist again once every track has played
+  pub repeat: bool,
+}
+
+impl Default for MusicConfig {
+  fn default() -> Self {
+    Self {
+      tracks: Vec::new(),
+      shuffle: false,
+      repeat: true,
+    }
+  }
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct SpawnerConfig {
   pub frequency_min: f32,
   pub frequency_max: f32,
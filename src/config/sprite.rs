@@ -1,4 +1,4 @@
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SpriteConfig {
   pub name: String,
   //These are the raw sizes in the spritesheet
@@ -11,6 +11,80 @@ pub struct SpriteConfig {
   pub scaled_y: u32,
   pub scaled_width: u32,
   pub scaled_height: u32,
+  #[serde(default)]
+  pub flip_horizontal: bool,
+  #[serde(default)]
+  pub flip_vertical: bool,
+}
+
+///Sprites laid out on a regular grid
+#[derive(Debug, Deserialize, Serialize)]
+pub struct GridConfig {
+  pub cell_width: u32,
+  pub cell_height: u32,
+  //Space around the edge of the sheet
+  #[serde(default)]
+  pub margin: u32,
+  //Space between cells
+  #[serde(default)]
+  pub spacing: u32,
+  //Names for the cells left to right, top to bottom. Empty names are skipped
+  pub names: Vec<String>,
+  //Output size, the cell size if not set
+  #[serde(default)]
+  pub scaled_width: Option<u32>,
+  #[serde(default)]
+  pub scaled_height: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum AtlasLayout {
+  Grid(GridConfig),
+  //Every sprite has its own rect
+  Packed(Vec<SpriteConfig>),
+}
+
+///A spritesheet described by its own file in assets/texture
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AtlasConfig {
+  //Png relative to assets/texture
+  pub texture: String,
+  pub width: u32,
+  pub height: u32,
+  pub layout: AtlasLayout,
+}
+
+///What an animation does once it reaches the last frame
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum LoopMode {
+  //Back to the first frame
+  Loop,
+  //Stay on the last frame
+  Once,
+  //Play backwards to the first frame, then forwards again
+  PingPong,
+}
+
+impl Default for LoopMode {
+  fn default() -> Self {
+    LoopMode::Loop
+  }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FrameConfig {
+  //Name of one of the sprites
+  pub sprite: String,
+  //Seconds
+  pub duration: f32,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AnimationConfig {
+  pub name: String,
+  pub frames: Vec<FrameConfig>,
+  #[serde(default)]
+  pub mode: LoopMode,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -18,6 +92,49 @@ pub struct SpritesConfig {
   pub sheet_width: u32,
   pub sheet_height: u32,
   pub sprites: Vec<SpriteConfig>,
+  //Atlas files relative to assets/texture, sprites in them are used the same as the ones above
+  #[serde(default)]
+  pub atlases: Vec<String>,
+  #[serde(default)]
+  pub animations: Vec<AnimationConfig>,
+}
+
+impl GridConfig {
+  ///The sprites in the grid for a sheet of the given width
+  pub fn sprites(&self, sheet_width: u32) -> Vec<SpriteConfig> {
+    let stride_x = self.cell_width + self.spacing;
+    let stride_y = self.cell_height + self.spacing;
+    let columns = if stride_x == 0 {
+      0
+    } else {
+      (sheet_width.saturating_sub(self.margin * 2) + self.spacing) / stride_x
+    };
+    if columns == 0 {
+      return Vec::new();
+    }
+
+    self.names
+      .iter()
+      .enumerate()
+      .filter(|(_, name)| !name.is_empty())
+      .map(|(i, name)| {
+        let i = i as u32;
+        SpriteConfig {
+          name: name.clone(),
+          sheet_x: self.margin + (i % columns) * stride_x,
+          sheet_y: self.margin + (i / columns) * stride_y,
+          sheet_width: self.cell_width,
+          sheet_height: self.cell_height,
+          scaled_x: 0,
+          scaled_y: 0,
+          scaled_width: self.scaled_width.unwrap_or(self.cell_width),
+          scaled_height: self.scaled_height.unwrap_or(self.cell_height),
+          flip_horizontal: false,
+          flip_vertical: false,
+        }
+      })
+      .collect()
+  }
 }
 
 impl Default for SpritesConfig {
@@ -26,6 +143,8 @@ impl Default for SpritesConfig {
       sheet_width: 0,
       sheet_height: 0,
       sprites: Vec::new(),
+      atlases: Vec::new(),
+      animations: Vec::new(),
     }
   }
 }
\ No newline at end of file
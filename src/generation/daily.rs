@@ -0,0 +1,75 @@
+use chrono::{
+  Datelike,
+  NaiveDate,
+};
+
+use rand::Rng;
+
+use ::config::LevelConfig;
+
+use super::{
+  seeded_rng,
+  generate_level,
+  Ability,
+  LevelParams,
+};
+
+///Seed for the given day, it only depends on the date so everyone gets the same level
+pub fn daily_seed(date: NaiveDate) -> u64 {
+  date.year() as u64 * 10000 + date.month() as u64 * 100 + date.day() as u64
+}
+
+///The daily challenge level. Difficulty params are rolled from the seed too.
+pub fn daily_level(date: NaiveDate) -> LevelConfig {
+  let seed = daily_seed(date);
+  let mut rng = seeded_rng(seed);
+
+  let mut abilities = vec![Ability::Lift];
+  if rng.gen::<f32>() < 0.4 {
+    abilities.push(Ability::ChangeDirection);
+  }
+  if rng.gen::<f32>() < 0.4 {
+    abilities.push(Ability::Ram);
+  }
+
+  let params = LevelParams {
+    seed,
+    length: rng.gen_range(900.0, 1600.0),
+    gaps: rng.gen_range(1, 4),
+    hazard_density: rng.gen_range(0.2, 0.5),
+    abilities,
+  };
+
+  let mut level = generate_level(&params);
+  level.name = Some(format!("Daily challenge {}", date.format("%Y-%m-%d")));
+  level
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn seed_only_depends_on_the_date() {
+    assert_eq!(daily_seed(NaiveDate::from_ymd(2018, 11, 5)), 20181105);
+    assert_eq!(daily_seed(NaiveDate::from_ymd(2019, 1, 31)), 20190131);
+  }
+
+  #[test]
+  fn same_day_gives_same_level() {
+    let date = NaiveDate::from_ymd(2018, 11, 5);
+    let a = daily_level(date);
+    let b = daily_level(date);
+    assert_eq!(a, b);
+    assert_eq!(a.name, Some("Daily challenge 2018-11-05".to_string()));
+    assert_eq!(a.exits.as_ref().map(|s| s.list.len()), Some(1));
+    assert_eq!(a.spawners.as_ref().map(|s| s.list.len()), Some(1));
+  }
+
+  #[test]
+  fn different_days_give_different_levels() {
+    let a = daily_level(NaiveDate::from_ymd(2018, 11, 5));
+    let b = daily_level(NaiveDate::from_ymd(2018, 11, 6));
+    assert!(a.walls != b.walls || a.deadly_areas != b.deadly_areas);
+  }
+}
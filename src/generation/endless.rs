@@ -0,0 +1,168 @@
+use amethyst::core::cgmath::Vector3;
+
+use rand::{
+  Rng,
+  prng::XorShiftRng,
+};
+
+use ::config::{
+  CameraOverrides,
+  LevelConfig,
+  SpawnOverides,
+};
+
+use super::{
+  seeded_rng,
+  cuboid,
+  set,
+  empty_level,
+  floor,
+  spawner,
+  FLOOR_Y,
+  HAZARD_HEIGHT,
+  PIT_DEPTH,
+  WALL_COLOR,
+  DEADLY_COLOR,
+  SPAWNER_COLOR,
+};
+
+pub const CHUNK_WIDTH: f32 = 400.0;
+//Each chunk is split into slots that hold at most one feature so they never overlap
+const CHUNK_SLOTS: u32 = 4;
+//Chunks get harder up to this index then stay the same
+const MAX_DIFFICULTY_CHUNK: u32 = 20;
+//Every n chunks get a spawner to keep the numbers up
+const SPAWNER_INTERVAL: u32 = 5;
+
+const START_LENGTH: f32 = 400.0;
+const BACK_WALL_HEIGHT: f32 = 600.0;
+
+const GAP_MIN: f32 = 30.0;
+//Has to stay below the slot width (CHUNK_WIDTH / CHUNK_SLOTS)
+const GAP_MAX: f32 = 90.0;
+const OBSTACLE_WIDTH: f32 = 20.0;
+const OBSTACLE_MIN: f32 = 20.0;
+const OBSTACLE_MAX: f32 = 80.0;
+const HAZARD_MIN: f32 = 20.0;
+const HAZARD_MAX: f32 = 80.0;
+
+const SPAWN_FREQUENCY: f32 = 0.8;
+const SPAWNS_PER_SPAWNER: u32 = 10;
+
+///Generates the terrain for endless mode one chunk at a time. The same seed always gives the same chunks.
+pub struct ChunkGenerator {
+  rng: XorShiftRng,
+  next_index: u32,
+  next_x: f32,
+}
+
+impl ChunkGenerator {
+  pub fn new(seed: u64) -> Self {
+    Self {
+      rng: seeded_rng(seed),
+      next_index: 0,
+      next_x: START_LENGTH,
+    }
+  }
+
+  ///Where the next chunk will start
+  pub fn generated_to(&self) -> f32 {
+    self.next_x
+  }
+
+  ///The next chunk of terrain (as a level so the usual level loading can create it)
+  pub fn next_chunk(&mut self) -> LevelConfig {
+    let x0 = self.next_x;
+    let index = self.next_index;
+    self.next_x += CHUNK_WIDTH;
+    self.next_index += 1;
+
+    //0 -> 1
+    let difficulty = index.min(MAX_DIFFICULTY_CHUNK) as f32 / MAX_DIFFICULTY_CHUNK as f32;
+    let gap_chance = 0.15 + 0.2 * difficulty;
+    let obstacle_chance = 0.15 + 0.15 * difficulty;
+    let hazard_chance = 0.1 + 0.2 * difficulty;
+
+    let mut walls = Vec::new();
+    let mut deadly_areas = Vec::new();
+    let mut spawners = Vec::new();
+
+    let slot_width = CHUNK_WIDTH / CHUNK_SLOTS as f32;
+    let mut floor_start = x0;
+
+    //First slot is always flat so there's somewhere to land coming out of the previous chunk
+    for slot in 1..CHUNK_SLOTS {
+      let slot_x = x0 + slot as f32 * slot_width;
+      let roll = self.rng.gen::<f32>();
+
+      if roll < gap_chance {
+        let gap = self.rng.gen_range(GAP_MIN, GAP_MIN + (GAP_MAX - GAP_MIN) * (0.25 + 0.75 * difficulty));
+        let gap_x = slot_x + (slot_width - gap) * 0.5;
+        walls.push(floor(floor_start, gap_x));
+        floor_start = gap_x + gap;
+
+        deadly_areas.push(cuboid(
+          gap_x + gap * 0.5,
+          FLOOR_Y - PIT_DEPTH,
+          gap,
+          HAZARD_HEIGHT));
+      } else if roll < gap_chance + obstacle_chance {
+        let height = self.rng.gen_range(OBSTACLE_MIN, OBSTACLE_MIN + (OBSTACLE_MAX - OBSTACLE_MIN) * (0.25 + 0.75 * difficulty));
+        walls.push(cuboid(
+          slot_x + slot_width * 0.5,
+          FLOOR_Y + height * 0.5,
+          OBSTACLE_WIDTH,
+          height));
+      } else if roll < gap_chance + obstacle_chance + hazard_chance {
+        let width = self.rng.gen_range(HAZARD_MIN, HAZARD_MIN + (HAZARD_MAX - HAZARD_MIN) * (0.25 + 0.75 * difficulty));
+        deadly_areas.push(cuboid(
+          slot_x + slot_width * 0.5,
+          FLOOR_Y + HAZARD_HEIGHT * 0.5,
+          width,
+          HAZARD_HEIGHT));
+      }
+    }
+    walls.push(floor(floor_start, x0 + CHUNK_WIDTH));
+
+    if index > 0 && index % SPAWNER_INTERVAL == 0 {
+      spawners.push(spawner(x0 + slot_width * 0.5));
+    }
+
+    let mut level = empty_level();
+    level.walls = set(walls, WALL_COLOR);
+    level.deadly_areas = set(deadly_areas, DEADLY_COLOR);
+    level.spawners = set(spawners, SPAWNER_COLOR);
+    level.spawn_overrides = Some(spawn_overrides());
+    level
+  }
+}
+
+///The level an endless run starts on. Everything after it comes from ChunkGenerator
+pub fn endless_start(seed: u64) -> LevelConfig {
+  let mut level = empty_level();
+  level.name = Some("Endless".to_string());
+  level.description = Some(format!("How far can you get? Seed {}", seed));
+  level.walls = set(vec![
+    floor(0.0, START_LENGTH),
+    cuboid(5.0, FLOOR_Y + BACK_WALL_HEIGHT * 0.5, 10.0, BACK_WALL_HEIGHT),
+  ], WALL_COLOR);
+  level.spawners = set(vec![spawner(50.0)], SPAWNER_COLOR);
+  level.spawn_overrides = Some(spawn_overrides());
+  level.camera_overrides = Some(CameraOverrides {
+    offset: None,
+    convergence_speed: None,
+    position: Some(Vector3::new(150.0, 100.0, 300.0)),
+    //Always follow the matriarch, there's no exit to look at
+    final_position: Some(None),
+  });
+  level
+}
+
+fn spawn_overrides() -> SpawnOverides {
+  SpawnOverides {
+    freq: SPAWN_FREQUENCY,
+    max: SPAWNS_PER_SPAWNER,
+    //There's no winning, only getting further
+    win_ratio: 0.0,
+  }
+}
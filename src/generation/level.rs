@@ -0,0 +1,475 @@
+use std::str::FromStr;
+
+use amethyst::core::cgmath::Vector3;
+
+use rand::Rng;
+
+use ron::ser::{
+  self,
+  PrettyConfig,
+};
+
+use ::config::{
+  CameraOverrides,
+  Cuboid,
+  LevelConfig,
+  ScoringConfig,
+};
+
+use super::{
+  seeded_rng,
+  cuboid,
+  set,
+  empty_level,
+  floor,
+  spawner,
+  FLOOR_Y,
+  HAZARD_HEIGHT,
+  PIT_DEPTH,
+  WALL_COLOR,
+  DEADLY_COLOR,
+  EXIT_COLOR,
+  SPAWNER_COLOR,
+};
+
+//Room at the start for the spawner and at the end for the exit, features only go between them
+const START_LENGTH: f32 = 200.0;
+const END_LENGTH: f32 = 150.0;
+//Each slot holds at most one feature
+const SLOT_WIDTH: f32 = 100.0;
+const WALL_HEIGHT: f32 = 600.0;
+
+const GAP_MIN: f32 = 30.0;
+const GAP_MAX: f32 = 90.0;
+const HAZARD_MIN: f32 = 20.0;
+const HAZARD_MAX: f32 = 70.0;
+//Walkers can't get over anything this tall without a lift
+const LIFT_WALL_MIN: f32 = 85.0;
+const LIFT_WALL_MAX: f32 = 110.0;
+const LIFT_WALL_WIDTH: f32 = 30.0;
+const RAM_BLOCK_SIZE: f32 = 40.0;
+const EXIT_SIZE: f32 = 25.0;
+
+///Abilities a generated level can be built to require
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ability {
+  Lift,
+  ChangeDirection,
+  Ram,
+}
+
+impl FromStr for Ability {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "lift" => Ok(Ability::Lift),
+      "change_direction" => Ok(Ability::ChangeDirection),
+      "ram" => Ok(Ability::Ram),
+      _ => Err(format!("Unknown ability '{}', expected lift, change_direction or ram", s)),
+    }
+  }
+}
+
+///What to generate. The same params always give the same level.
+#[derive(Debug, Clone)]
+pub struct LevelParams {
+  pub seed: u64,
+  //Total width of the level, gets extended if the features don't fit
+  pub length: f32,
+  pub gaps: u32,
+  //Chance (0 -> 1) of each free slot getting a hazard
+  pub hazard_density: f32,
+  pub abilities: Vec<Ability>,
+}
+
+impl Default for LevelParams {
+  fn default() -> Self {
+    Self {
+      seed: 0,
+      length: 1000.0,
+      gaps: 2,
+      hazard_density: 0.3,
+      abilities: vec![Ability::Lift],
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Feature {
+  Gap,
+  Hazard,
+  LiftWall,
+  RamBlock,
+}
+
+///Builds a complete level from the params. Everything is laid out left to right then mirrored if the
+///creeps need to turn around to reach the exit.
+pub fn generate_level(params: &LevelParams) -> LevelConfig {
+  let mut rng = seeded_rng(params.seed);
+  let turn_around = params.abilities.contains(&Ability::ChangeDirection);
+
+  let mut features = vec![Feature::Gap; params.gaps as usize];
+  if params.abilities.contains(&Ability::Lift) {
+    features.push(Feature::LiftWall);
+  }
+  if params.abilities.contains(&Ability::Ram) {
+    features.push(Feature::RamBlock);
+  }
+
+  let mut slots = ((params.length - START_LENGTH - END_LENGTH) / SLOT_WIDTH).max(0.0) as usize;
+  if slots < features.len() {
+    warn!("Level length {} is too short for {} features, extending it", params.length, features.len());
+    slots = features.len();
+  }
+  let length = START_LENGTH + END_LENGTH + slots as f32 * SLOT_WIDTH;
+
+  let mut layout: Vec<Option<Feature>> = features.iter().map(|f| Some(*f)).collect();
+  while layout.len() < slots {
+    layout.push(None);
+  }
+  rng.shuffle(&mut layout);
+  for slot in layout.iter_mut().filter(|slot| slot.is_none()) {
+    if rng.gen::<f32>() < params.hazard_density {
+      *slot = Some(Feature::Hazard);
+    }
+  }
+
+  let mut walls = Vec::new();
+  let mut deadly_areas = Vec::new();
+  let mut blocks = Vec::new();
+
+  //Start area. When turning around the creeps spawn heading into a pit
+  let spawner_x;
+  let mut floor_start;
+  walls.push(cuboid(5.0, FLOOR_Y + WALL_HEIGHT * 0.5, 10.0, WALL_HEIGHT));
+  if turn_around {
+    spawner_x = 150.0;
+    walls.push(floor(0.0, 20.0));
+    deadly_areas.push(cuboid(60.0, FLOOR_Y - PIT_DEPTH, 80.0, HAZARD_HEIGHT));
+    floor_start = 100.0;
+  } else {
+    spawner_x = 50.0;
+    floor_start = 0.0;
+  }
+
+  for (i, feature) in layout.iter().enumerate() {
+    let slot_x = START_LENGTH + i as f32 * SLOT_WIDTH;
+    let center = slot_x + SLOT_WIDTH * 0.5;
+
+    match feature {
+      Some(Feature::Gap) => {
+        let gap = rng.gen_range(GAP_MIN, GAP_MAX);
+        let gap_x = center - gap * 0.5;
+        walls.push(floor(floor_start, gap_x));
+        floor_start = gap_x + gap;
+        deadly_areas.push(cuboid(center, FLOOR_Y - PIT_DEPTH, gap, HAZARD_HEIGHT));
+      },
+      Some(Feature::Hazard) => {
+        let width = rng.gen_range(HAZARD_MIN, HAZARD_MAX);
+        deadly_areas.push(cuboid(center, FLOOR_Y + HAZARD_HEIGHT * 0.5, width, HAZARD_HEIGHT));
+      },
+      Some(Feature::LiftWall) => {
+        let height = rng.gen_range(LIFT_WALL_MIN, LIFT_WALL_MAX);
+        walls.push(cuboid(center, FLOOR_Y + height * 0.5, LIFT_WALL_WIDTH, height));
+      },
+      Some(Feature::RamBlock) => {
+        blocks.push(cuboid(center, FLOOR_Y + RAM_BLOCK_SIZE * 0.5, RAM_BLOCK_SIZE, RAM_BLOCK_SIZE));
+      },
+      None => {},
+    }
+  }
+
+  //End area
+  walls.push(floor(floor_start, length));
+  walls.push(cuboid(length - 5.0, FLOOR_Y + WALL_HEIGHT * 0.5, 10.0, WALL_HEIGHT));
+  let exit_x = length - END_LENGTH * 0.5;
+  let exits = vec![cuboid(exit_x, FLOOR_Y + EXIT_SIZE * 0.5, EXIT_SIZE, EXIT_SIZE)];
+  let spawners = vec![spawner(spawner_x)];
+
+  let mut level = empty_level();
+  level.name = Some(format!("Generated #{}", params.seed));
+  level.description = Some(describe(params));
+  level.walls = set(walls, WALL_COLOR);
+  level.deadly_areas = set(deadly_areas, DEADLY_COLOR);
+  level.exits = set(exits, EXIT_COLOR);
+  level.spawners = set(spawners, SPAWNER_COLOR);
+  level.blocks = set(blocks, WALL_COLOR);
+
+  if turn_around {
+    mirror(&mut level, length);
+  }
+
+  let (spawner_x, exit_x) = if turn_around {
+    (length - spawner_x, length - exit_x)
+  } else {
+    (spawner_x, exit_x)
+  };
+  level.camera_overrides = Some(CameraOverrides {
+    offset: None,
+    convergence_speed: None,
+    position: Some(Vector3::new(spawner_x, 100.0, 300.0)),
+    final_position: Some(Some(Vector3::new(exit_x, 100.0, 300.0))),
+  });
+
+  //Every gap and required ability needs at least one use
+  let par_abilities = params.gaps + params.abilities.len() as u32;
+  level.scoring = Some(ScoringConfig {
+    par_time: None,
+    par_abilities: Some(par_abilities),
+    bronze: None,
+    silver: Some(0.75),
+    gold: Some(0.95),
+  });
+
+  level
+}
+
+///The level in the same RON format as resources/config.ron
+pub fn level_to_ron(level: &LevelConfig) -> Result<String, ser::Error> {
+  ser::to_string_pretty(level, PrettyConfig::default())
+}
+
+///Swaps the index'th entry of the levels list in the text of resources/config.ron for level_ron.
+///The rest of the file is left as is, comments included. None if that level can't be found
+pub fn replace_level_ron(config: &str, index: usize, level_ron: &str) -> Option<String> {
+  let (start, end) = find_level(config, index)?;
+  //Line the level up with the entry it replaces
+  let line_start = config[..start].rfind('\n').map_or(0, |i| i + 1);
+  let indent: String = config[line_start..start].chars().take_while(|c| c.is_whitespace()).collect();
+  let mut level_ron = level_ron.trim().replace('\n', &format!("\n{}", indent));
+  //Keep the //N comment after the opening bracket that the hand made levels have
+  let first_line = config[start + 1..end].lines().next().unwrap_or("").trim();
+  if first_line.starts_with("//") && level_ron.starts_with("(\n") {
+    level_ron.insert_str(1, &format!(" {}", first_line));
+  }
+  Some(format!("{}{}{}", &config[..start], level_ron, &config[end..]))
+}
+
+//Byte range of the index'th level, that's the index'th tuple in the list under levels: (levels: [...])
+fn find_level(config: &str, index: usize) -> Option<(usize, usize)> {
+  let bytes = config.as_bytes();
+  let mut depth = 0;
+  let mut section_depth = None;
+  let mut list_depth = None;
+  let mut count = 0;
+  let mut start = None;
+  let mut i = 0;
+  while i < bytes.len() {
+    match bytes[i] {
+      b'"' => {
+        i += 1;
+        while i < bytes.len() && bytes[i] != b'"' {
+          if bytes[i] == b'\\' {
+            i += 1;
+          }
+          i += 1;
+        }
+      },
+      b'/' if bytes.get(i + 1) == Some(&b'/') => {
+        while i < bytes.len() && bytes[i] != b'\n' {
+          i += 1;
+        }
+      },
+      b'/' if bytes.get(i + 1) == Some(&b'*') => {
+        i = config[i..].find("*/").map_or(bytes.len(), |end| i + end + 1);
+      },
+      b'(' | b'[' | b'{' => {
+        if list_depth == Some(depth) && bytes[i] == b'(' {
+          if count == index {
+            start = Some(i);
+          }
+          count += 1;
+        }
+        depth += 1;
+      },
+      b')' | b']' | b'}' => {
+        if depth == 0 {
+          return None;
+        }
+        depth -= 1;
+        if list_depth == Some(depth) {
+          if let Some(start) = start {
+            return Some((start, i + 1));
+          }
+        } else if list_depth == Some(depth + 1) {
+          //End of the list
+          return None;
+        } else if section_depth == Some(depth + 1) {
+          section_depth = None;
+        }
+      },
+      b'l' if config[i..].starts_with("levels")
+        && !(i > 0 && (bytes[i - 1].is_ascii_alphanumeric() || bytes[i - 1] == b'_')) => {
+        i += "levels".len();
+        let rest = config[i..].trim_start();
+        if rest.starts_with(':') {
+          match (rest[1..].trim_start().chars().next(), depth) {
+            (Some('('), 1) => section_depth = Some(2),
+            (Some('['), 2) if section_depth == Some(2) => list_depth = Some(3),
+            _ => (),
+          }
+        }
+        continue;
+      },
+      _ => (),
+    }
+    i += 1;
+  }
+  None
+}
+
+fn describe(params: &LevelParams) -> String {
+  if params.abilities.is_empty() {
+    "Watch your step".to_string()
+  } else {
+    let names: Vec<&str> = params.abilities
+      .iter()
+      .map(|ability| match ability {
+        Ability::Lift => "lift",
+        Ability::ChangeDirection => "change direction",
+        Ability::Ram => "ram",
+      })
+      .collect();
+    format!("You'll need: {}", names.join(", "))
+  }
+}
+
+fn mirror(level: &mut LevelConfig, length: f32) {
+  let sets = vec![
+    &mut level.walls,
+    &mut level.deadly_areas,
+    &mut level.exits,
+    &mut level.spawners,
+    &mut level.blocks,
+  ];
+  for set in sets {
+    if let Some(set) = set {
+      for o in &mut set.list {
+        mirror_cuboid(o, length);
+      }
+    }
+  }
+}
+
+fn mirror_cuboid(o: &mut Cuboid, length: f32) {
+  o.position.x = length - o.position.x;
+  o.rotation = o.rotation.map(|r| -r);
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use ::config::CuboidSet;
+
+  fn params(seed: u64, abilities: Vec<Ability>) -> LevelParams {
+    LevelParams {
+      seed,
+      abilities,
+      ..LevelParams::default()
+    }
+  }
+
+  fn count(set: &Option<CuboidSet>) -> usize {
+    set.as_ref().map_or(0, |s| s.list.len())
+  }
+
+  #[test]
+  fn same_seed_gives_same_level() {
+    for seed in &[0, 1, 42, u64::max_value()] {
+      let p = params(*seed, vec![Ability::Lift, Ability::ChangeDirection, Ability::Ram]);
+      let a = generate_level(&p);
+      let b = generate_level(&p);
+      assert_eq!(a, b);
+      assert_eq!(level_to_ron(&a).unwrap(), level_to_ron(&b).unwrap());
+    }
+  }
+
+  #[test]
+  fn different_seeds_give_different_levels() {
+    let a = generate_level(&params(1, vec![Ability::Lift]));
+    let b = generate_level(&params(2, vec![Ability::Lift]));
+    assert!(a.walls != b.walls || a.deadly_areas != b.deadly_areas);
+  }
+
+  #[test]
+  fn fixed_seed_has_every_feature() {
+    let p = LevelParams {
+      seed: 42,
+      length: 1000.0,
+      gaps: 2,
+      hazard_density: 0.0,
+      abilities: vec![Ability::Lift, Ability::Ram],
+    };
+    let level = generate_level(&p);
+
+    assert_eq!(level.name, Some("Generated #42".to_string()));
+    assert_eq!(count(&level.exits), 1);
+    assert_eq!(count(&level.spawners), 1);
+    //No hazards so the only deadly areas are the pits under the gaps
+    assert_eq!(count(&level.deadly_areas), 2);
+    assert_eq!(count(&level.blocks), 1);
+    assert_eq!(level.scoring.as_ref().and_then(|s| s.par_abilities), Some(4));
+
+    let lift_walls = level.walls
+      .as_ref()
+      .unwrap()
+      .list
+      .iter()
+      .filter(|o| o.size.x == LIFT_WALL_WIDTH && o.size.y >= LIFT_WALL_MIN && o.size.y < LIFT_WALL_MAX)
+      .count();
+    assert_eq!(lift_walls, 1);
+  }
+
+  #[test]
+  fn turning_around_puts_the_exit_behind_the_spawner() {
+    let level = generate_level(&params(7, vec![Ability::ChangeDirection]));
+    let spawner_x = level.spawners.as_ref().unwrap().list[0].position.x;
+    let exit_x = level.exits.as_ref().unwrap().list[0].position.x;
+    assert!(exit_x < spawner_x);
+  }
+
+  #[test]
+  fn replacing_a_level_keeps_the_rest_of_the_config() {
+    let config = "#![enable(implicit_some)]
+(
+  pawn: (velocity: 64.0), //levels: [
+  levels: (
+    start_level: 0,
+    levels: [
+      ( //0
+        name: \"Lift them (up)\",
+      ),
+      ( /* ) */
+        name: \"Second\",
+        walls: (list: []),
+      ),
+    ],
+  ),
+)";
+    let replaced = replace_level_ron(config, 1, "(\n    name: \"Edited\",\n)").unwrap();
+    assert!(replaced.contains("pawn: (velocity: 64.0), //levels: ["));
+    assert!(replaced.contains("( //0\n        name: \"Lift them (up)\",\n      ),"));
+    assert!(replaced.contains("      (\n          name: \"Edited\",\n      ),\n    ],"));
+    assert!(!replaced.contains("Second"));
+
+    let replaced = replace_level_ron(config, 0, "(\n    name: \"Edited\",\n)").unwrap();
+    assert!(replaced.contains("      ( //0\n          name: \"Edited\",\n      ),\n      ( /* ) */"));
+
+    assert_eq!(replace_level_ron(config, 2, "()"), None);
+  }
+
+  #[test]
+  fn short_levels_are_extended_to_fit() {
+    let p = LevelParams {
+      seed: 3,
+      length: 0.0,
+      gaps: 4,
+      hazard_density: 0.0,
+      abilities: vec![Ability::Lift],
+    };
+    let level = generate_level(&p);
+    let exit_x = level.exits.as_ref().unwrap().list[0].position.x;
+    assert_eq!(exit_x, START_LENGTH + 5.0 * SLOT_WIDTH + END_LENGTH * 0.5);
+  }
+}
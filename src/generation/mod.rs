@@ -0,0 +1,99 @@
+use amethyst::core::cgmath::Vector3;
+
+use rand::{
+  SeedableRng,
+  prng::XorShiftRng,
+};
+
+use ::{
+  config::{
+    Cuboid,
+    CuboidSet,
+    LevelConfig,
+  },
+  components::Color,
+};
+
+mod endless;
+mod level;
+mod daily;
+
+pub use self::endless::*;
+pub use self::level::*;
+pub use self::daily::*;
+
+//Same palette as the hand made levels
+const WALL_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 1.0];
+const DEADLY_COLOR: [f32; 4] = [0.8, 0.1, 0.1, 1.0];
+const EXIT_COLOR: [f32; 4] = [0.1, 0.8, 0.1, 1.0];
+const SPAWNER_COLOR: [f32; 4] = [0.1, 0.1, 0.8, 1.0];
+
+const FLOOR_THICKNESS: f32 = 20.0;
+//Top surface of the floor
+pub const FLOOR_Y: f32 = 20.0;
+const HAZARD_HEIGHT: f32 = 10.0;
+//How far below the floor the deadly areas under gaps go
+const PIT_DEPTH: f32 = 80.0;
+
+///Creates an rng that produces the same sequence for a given seed on every platform
+pub fn seeded_rng(seed: u64) -> XorShiftRng {
+  //XorShift can't be seeded with all zeros, mixing in a constant for the second half makes that impossible
+  let mixed = seed ^ 0x9E37_79B9_7F4A_7C15;
+  let mut bytes = [0u8; 16];
+  for i in 0..8 {
+    bytes[i] = (seed >> (i * 8)) as u8;
+    bytes[i + 8] = (mixed >> (i * 8)) as u8;
+  }
+  XorShiftRng::from_seed(bytes)
+}
+
+fn color(c: [f32; 4]) -> Option<Color> {
+  Some(Color::new(c[0], c[1], c[2], c[3]))
+}
+
+///Cuboid centered on x, y
+fn cuboid(x: f32, y: f32, width: f32, height: f32) -> Cuboid {
+  Cuboid {
+    size: Vector3::new(width, height, 0.1),
+    position: Vector3::new(x, y, 0.0),
+    color: None,
+    rotation: None,
+  }
+}
+
+fn floor(from: f32, to: f32) -> Cuboid {
+  cuboid((from + to) * 0.5, FLOOR_Y - FLOOR_THICKNESS * 0.5, to - from, FLOOR_THICKNESS)
+}
+
+fn spawner(x: f32) -> Cuboid {
+  cuboid(x, FLOOR_Y + 40.0, 30.0, 30.0)
+}
+
+fn set(list: Vec<Cuboid>, c: [f32; 4]) -> Option<CuboidSet> {
+  if list.is_empty() {
+    None
+  } else {
+    Some(CuboidSet {
+      list,
+      color: color(c),
+    })
+  }
+}
+
+///A level with nothing in it, generators fill in the bits they need
+fn empty_level() -> LevelConfig {
+  LevelConfig {
+    name: None,
+    description: None,
+    walls: None,
+    deadly_areas: None,
+    exits: None,
+    spawners: None,
+    blocks: None,
+    spawn_overrides: None,
+    camera_overrides: None,
+    scoring: None,
+    music: None,
+    bounds: None,
+  }
+}
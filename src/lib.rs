@@ -8,6 +8,9 @@ extern crate ncollide2d;
 extern crate nphysics2d;
 
 extern crate random_color;
+extern crate dirs;
+extern crate ron;
+extern crate rodio;
 
 #[allow(unused_imports)]
 #[macro_use]
@@ -46,10 +49,16 @@ use states::{
 
 mod components;
 mod resources;
+//Public so the level generator binary can use it
+pub mod generation;
+use resources::{
+  SaveData,
+  AudioSettings,
+  DailyHistory,
+};
 
-fn create_logger(level: LevelFilter) {
-  use std::io;
-
+//Output is a parameter so the level generator binary can log to stderr and keep stdout for the levels
+pub fn create_logger<T: Into<fern::Output>>(level: LevelFilter, output: T) {
   let gfx_device_gl_level = if level > LevelFilter::Warn {
     LevelFilter::Warn
   } else {
@@ -58,7 +67,7 @@ fn create_logger(level: LevelFilter) {
 
   let color_config = fern::colors::ColoredLevelConfig::new();
   fern::Dispatch::new()
-    .chain(io::stdout())
+    .chain(output)
     .level(level)
     .level_for("gfx_device_gl", gfx_device_gl_level)
     .format(move |out, message, record| {
@@ -85,7 +94,7 @@ pub fn run() -> Result<(), amethyst::Error> {
   let game_config = load_game_config().expect("GameConfig failed to load");
 
   //Custom create log to silence "Created buffer" spam every frame
-  create_logger(game_config.log_level);
+  create_logger(game_config.log_level, std::io::stdout());
 
   let display_config = DisplayConfig::load(&format!("{}/resources/display_config.ron", app_root));
 
@@ -107,6 +116,9 @@ pub fn run() -> Result<(), amethyst::Error> {
     .with_resource(game_config.sound)
     .with_resource(game_config.sprites)
     .with_resource(game_config.levels)
+    .with_resource(SaveData::load_user())
+    .with_resource(AudioSettings::load_user())
+    .with_resource(DailyHistory::load_user())
     .build(game_data)?;
 
   game.run();
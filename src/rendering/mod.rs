@@ -27,6 +27,7 @@ pub fn configure_rendering<'a, 'b>(builder: GameDataBuilder<'a, 'b>, display_con
       .clear_target([0.05, 0.05, 0.05, 1.0], 1.0)
       .with_pass(DrawShaded::<PosNormTex>::new())
       .with_pass(DrawUi::new())
+      //Sprites are the only thing drawn with blending, the ghost is a see through sprite for that reason
       .with_pass(DrawSprite::new()
         .with_transparency(ColorMask::all(), ALPHA, None))
       .with_pass(DrawDebugLines::<PosColorNorm>::new())
@@ -0,0 +1,72 @@
+use std::{
+  fs,
+  path::PathBuf,
+};
+
+use amethyst::config::Config;
+
+use ::config::SoundConfig;
+
+use super::{
+  user_data_dir,
+  Sounds,
+};
+
+const SETTINGS_FILE: &'static str = "audio.ron";
+
+///Resource with the player's audio settings. Anything that's not set falls back to the volume in SoundConfig
+///so changing the shipped defaults still affects players who've never touched that slider.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct AudioSettings {
+  #[serde(default)]
+  pub volume: Option<f32>,
+  #[serde(default)]
+  pub music_volume: Option<f32>,
+  #[serde(default)]
+  pub sfx_volume: Option<f32>,
+  #[serde(default)]
+  pub muted: bool,
+}
+
+impl AudioSettings {
+  ///Loads the settings file, if there isn't one (or it's broken) the shipped defaults are used
+  pub fn load_user() -> Self {
+    let path = Self::path();
+    if !path.exists() {
+      return Self::default();
+    }
+
+    match Self::load_no_fallback(&path) {
+      Ok(settings) => settings,
+      Err(e) => {
+        error!("Failed to load audio settings {:?}: {}", path, e);
+        Self::default()
+      },
+    }
+  }
+
+  pub fn save(&self) {
+    let path = Self::path();
+    if let Err(e) = fs::create_dir_all(user_data_dir()) {
+      error!("Failed to create save directory for {:?}: {}", path, e);
+      return;
+    }
+
+    match self.write(&path) {
+      Ok(_) => debug!("Saved audio settings to {:?}", path),
+      Err(e) => error!("Failed to write audio settings {:?}: {}", path, e),
+    }
+  }
+
+  fn path() -> PathBuf {
+    user_data_dir().join(SETTINGS_FILE)
+  }
+
+  ///Sets the volumes on sounds, using the config for anything the player hasn't changed
+  pub fn apply(&self, sound_config: &SoundConfig, sounds: &mut Sounds) {
+    sounds.volume = self.volume.unwrap_or(sound_config.volume);
+    sounds.music_volume = self.music_volume.unwrap_or(sound_config.music_volume);
+    sounds.sfx_volume = self.sfx_volume.unwrap_or(sound_config.sfx_volume);
+    sounds.muted = self.muted;
+  }
+}
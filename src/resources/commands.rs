@@ -1,5 +1,7 @@
 use amethyst::shrev::EventChannel;
 
+use chrono::NaiveDate;
+
 ///Commands that various systems listen for. Most are user input.
 #[derive(Debug)]
 pub enum Command {
@@ -14,6 +16,25 @@ pub enum Command {
   NextLevel,
   RestartLevel,
   PreviousLevel,
+  //Jumps straight to the level at this index
+  LoadLevel(usize),
+  //Starts an endless run with the given seed
+  StartEndless(u64),
+  //Starts the daily challenge for the given date
+  StartDaily(NaiveDate),
+  //Starts a time attack run from the first level
+  StartTimeAttack,
+  //Unloads the level without loading another, for going back to the main menu
+  UnloadLevel,
+  //Writes the level in progress to disk
+  SaveLevelState,
+  //Restores the level written by SaveLevelState
+  LoadLevelState,
+  //Steps the time scale down/up through PhysicsConfig.time_scales
+  SlowDown,
+  SpeedUp,
+  //Switches the camera between perspective and orthographic
+  ToggleProjection,
   Exodus,
 }
 
@@ -31,6 +52,16 @@ pub fn also_kills(cmd: &Command) -> bool {
     &Command::NextLevel => false,
     &Command::RestartLevel => false,
     &Command::PreviousLevel => false,
+    &Command::LoadLevel(_) => false,
+    &Command::StartEndless(_) => false,
+    &Command::StartDaily(_) => false,
+    &Command::StartTimeAttack => false,
+    &Command::UnloadLevel => false,
+    &Command::SaveLevelState => false,
+    &Command::LoadLevelState => false,
+    &Command::SlowDown => false,
+    &Command::SpeedUp => false,
+    &Command::ToggleProjection => false,
     &Command::Exodus => false,
   }
 }
@@ -0,0 +1,141 @@
+use std::{
+  collections::BTreeMap,
+  fs,
+  path::PathBuf,
+};
+
+use amethyst::config::Config;
+
+use chrono::{
+  Duration,
+  NaiveDate,
+};
+
+use super::user_data_dir;
+
+const HISTORY_FILE: &'static str = "daily.ron";
+const DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+///Best result for one day's challenge
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct DailyRecord {
+  pub attempts: u32,
+  pub completed: bool,
+  //Only counts completed runs, 0 if it's never been completed
+  pub best_score: u32,
+}
+
+///Resource with the results of every daily challenge the player has attempted. Kept separate from the
+///save data so it can be wiped without losing campaign progress.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct DailyHistory {
+  //Keyed by date (yyyy-mm-dd) so it sorts and reads nicely in the file
+  pub days: BTreeMap<String, DailyRecord>,
+}
+
+///Score for a completed daily run. Saving creeps is what matters, speed breaks ties.
+pub fn daily_score(saved: u32, clear_time: f32) -> u32 {
+  saved * 100 + (300.0 - clear_time).max(0.0) as u32
+}
+
+impl DailyHistory {
+  ///Loads the history file, if there isn't one (or it's broken) the history starts empty
+  pub fn load_user() -> Self {
+    let path = Self::path();
+    if !path.exists() {
+      return Self::default();
+    }
+
+    match Self::load_no_fallback(&path) {
+      Ok(history) => history,
+      Err(e) => {
+        error!("Failed to load daily history {:?}: {}", path, e);
+        Self::default()
+      },
+    }
+  }
+
+  pub fn save(&self) {
+    let path = Self::path();
+    if let Err(e) = fs::create_dir_all(user_data_dir()) {
+      error!("Failed to create save directory for {:?}: {}", path, e);
+      return;
+    }
+
+    match self.write(&path) {
+      Ok(_) => debug!("Saved daily history to {:?}", path),
+      Err(e) => error!("Failed to write daily history {:?}: {}", path, e),
+    }
+  }
+
+  fn path() -> PathBuf {
+    user_data_dir().join(HISTORY_FILE)
+  }
+
+  pub fn get(&self, date: NaiveDate) -> Option<&DailyRecord> {
+    self.days.get(&key(date))
+  }
+
+  ///Records an attempt at the day's challenge, score is only used if it was completed
+  pub fn record(&mut self, date: NaiveDate, completed: bool, score: u32) {
+    let record = self.days
+      .entry(key(date))
+      .or_insert_with(DailyRecord::default);
+    record.attempts += 1;
+    if completed {
+      record.completed = true;
+      record.best_score = record.best_score.max(score);
+    }
+  }
+
+  fn completed(&self, date: NaiveDate) -> bool {
+    self.get(date).map_or(false, |r| r.completed)
+  }
+
+  ///Consecutive completed days up to today. Today not being done yet doesn't break the streak.
+  pub fn streak(&self, today: NaiveDate) -> u32 {
+    let mut date = if self.completed(today) {
+      today
+    } else {
+      today - Duration::days(1)
+    };
+
+    let mut streak = 0;
+    while self.completed(date) {
+      streak += 1;
+      date = date - Duration::days(1);
+    }
+    streak
+  }
+
+  pub fn best_streak(&self) -> u32 {
+    let mut best = 0;
+    let mut current = 0;
+    let mut prev: Option<NaiveDate> = None;
+
+    //BTreeMap iterates in date order thanks to the key format
+    for (date, record) in &self.days {
+      let date = match NaiveDate::parse_from_str(date, DATE_FORMAT) {
+        Ok(date) => date,
+        Err(_) => continue,
+      };
+      if !record.completed {
+        current = 0;
+        prev = None;
+        continue;
+      }
+
+      current = match prev {
+        Some(prev) if prev + Duration::days(1) == date => current + 1,
+        _ => 1,
+      };
+      best = best.max(current);
+      prev = Some(date);
+    }
+    best
+  }
+}
+
+fn key(date: NaiveDate) -> String {
+  date.format(DATE_FORMAT).to_string()
+}
@@ -0,0 +1,35 @@
+use ::{
+  generation::ChunkGenerator,
+  resources::SCALE_PIXELS_PER_METER,
+};
+
+///Resource tracking the state of an endless run. Reset whenever an endless level is loaded.
+pub struct EndlessRun {
+  pub generator: ChunkGenerator,
+  //Furthest the matriarch has got (pixels from the start)
+  pub distance: f32,
+}
+
+impl Default for EndlessRun {
+  fn default() -> Self {
+    Self::new(0)
+  }
+}
+
+impl EndlessRun {
+  pub fn new(seed: u64) -> Self {
+    Self {
+      generator: ChunkGenerator::new(seed),
+      distance: 0.0,
+    }
+  }
+
+  pub fn distance_meters(&self) -> u32 {
+    (self.distance / SCALE_PIXELS_PER_METER) as u32
+  }
+
+  ///Meters travelled plus however many creeps are still going
+  pub fn score(&self, alive: u32) -> u32 {
+    self.distance_meters() + alive
+  }
+}
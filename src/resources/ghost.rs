@@ -0,0 +1,77 @@
+use std::{
+  fs,
+  path::PathBuf,
+};
+
+use amethyst::config::Config;
+
+use super::user_data_dir;
+
+const GHOST_DIR: &'static str = "ghosts";
+
+///Matriarch position (pixels) for every physics step of a run. None while there's no matriarch.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct GhostTrack {
+  pub points: Vec<Option<(f32, f32)>>,
+}
+
+impl GhostTrack {
+  ///The track of the best run of the level, if there is one
+  pub fn load_best(level: usize) -> Option<Self> {
+    let path = Self::path(level);
+    if !path.exists() {
+      return None;
+    }
+
+    match Self::load_no_fallback(&path) {
+      Ok(track) => Some(track),
+      Err(e) => {
+        error!("Failed to load ghost {:?}: {}", path, e);
+        None
+      },
+    }
+  }
+
+  pub fn save_best(&self, level: usize) {
+    let path = Self::path(level);
+    if let Err(e) = fs::create_dir_all(user_data_dir().join(GHOST_DIR)) {
+      error!("Failed to create ghost directory for {:?}: {}", path, e);
+      return;
+    }
+
+    match self.write(&path) {
+      Ok(_) => debug!("Saved ghost to {:?}", path),
+      Err(e) => error!("Failed to write ghost {:?}: {}", path, e),
+    }
+  }
+
+  fn path(level: usize) -> PathBuf {
+    user_data_dir()
+      .join(GHOST_DIR)
+      .join(format!("level_{}.ron", level))
+  }
+
+  ///Position at the given step, None if there's no matriarch then or the track has ended
+  pub fn get(&self, step: usize) -> Option<(f32, f32)> {
+    self.points.get(step).and_then(|p| *p)
+  }
+}
+
+///Resource holding the ghost being played back and the track being recorded for the current level.
+///Reset whenever a level is loaded.
+#[derive(Debug, Default)]
+pub struct GhostRun {
+  pub recording: GhostTrack,
+  pub best: Option<GhostTrack>,
+  //PhysicsWorld step count when the level started, set on the first frame the level is running
+  pub start_step: Option<u64>,
+}
+
+impl GhostRun {
+  pub fn new(best: Option<GhostTrack>) -> Self {
+    Self {
+      best,
+      ..Self::default()
+    }
+  }
+}
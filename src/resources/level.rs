@@ -1,23 +1,58 @@
+use chrono::NaiveDate;
+
 use ::config::{
   LevelConfig,
   CameraOverrides,
 };
 
+use super::LevelState;
+
 #[derive(PartialEq)]
 pub enum LoadState {
   NeedConfig,
+  //Config is loaded but no level is, waiting for a LoadLevel command
+  Unloaded,
   NeedLoad,
   Loaded,
   PhysicsCleanup,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+  //Every creep is accounted for and enough were saved
+  Won,
+  //Too many have died for the level to be completed
+  Lost,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GameMode {
+  //The hand made levels from the config
+  Campaign,
+  //Terrain generated ahead of the matriarch from the seed
+  Endless(u64),
+  //The generated level for that day
+  Daily(NaiveDate),
+}
+
 ///Resource that holds the list of levels and tracks which one is loaded
 pub struct Level {
   pub current_level: usize,
   pub levels: Vec<LevelConfig>,
+  pub mode: GameMode,
+  //The level that's actually loaded, this is only different from levels[current_level] for generated levels
+  pub loaded_config: Option<LevelConfig>,
   pub prev_camera_settings: Option<CameraOverrides>,
   pub load_state: LoadState,
   pub runtime: f32,
+  //Set once the current level has been won or lost
+  pub outcome: Option<Outcome>,
+  //Restored instead of building the level from its config on the next load
+  pub pending_state: Option<LevelState>,
+  //Set while the editor is open so reloading the config doesn't throw away its changes
+  pub editing: bool,
+  //Levels changed in the editor but not saved to the config yet, reloading the config keeps these as they are
+  pub unsaved_levels: Vec<usize>,
 }
 
 impl Default for Level {
@@ -25,9 +60,24 @@ impl Default for Level {
     Self {
       current_level: 0,
       levels: Vec::new(),
+      mode: GameMode::Campaign,
+      loaded_config: None,
       prev_camera_settings: None,
       load_state: LoadState::NeedConfig,
       runtime: 0.0,
+      outcome: None,
+      pending_state: None,
+      editing: false,
+      unsaved_levels: Vec::new(),
     }
   }
+}
+
+impl Level {
+  ///Config for the level that's loaded (or about to be)
+  pub fn config(&self) -> &LevelConfig {
+    self.loaded_config
+      .as_ref()
+      .unwrap_or(&self.levels[self.current_level])
+  }
 }
\ No newline at end of file
@@ -0,0 +1,117 @@
+use std::{
+  fs,
+  path::PathBuf,
+};
+
+use amethyst::config::Config;
+
+use ::components::{
+  Age,
+  ChangeDirection,
+  Color,
+  Direction,
+  Matriarch,
+  Spawner,
+  Walker,
+};
+
+use super::{
+  user_data_dir,
+  SpawnStats,
+};
+
+const LEVEL_STATE_FILE: &'static str = "level_state.ron";
+
+///Which level the state belongs to
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum SavedMode {
+  //Index into the level list
+  Campaign(usize),
+  //yyyy-mm-dd
+  Daily(String),
+}
+
+///How the physics side of an entity was created
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub enum SavedColliderKind {
+  Ground,
+  Sensor,
+  Body {
+    density: f32,
+  },
+}
+
+///One collider entity and every gameplay component on it. Positions and sizes are in pixels.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SavedEntity {
+  pub kind: SavedColliderKind,
+  pub size: (f32, f32),
+  pub position: (f32, f32),
+  pub rotation: f32,
+  //Linear x, y and angular (physics units), only for bodies
+  #[serde(default)]
+  pub velocity: Option<(f32, f32, f32)>,
+  #[serde(default)]
+  pub color: Option<Color>,
+  #[serde(default)]
+  pub walker: Option<Walker>,
+  #[serde(default)]
+  pub age: Option<Age>,
+  #[serde(default)]
+  pub matriarch: Option<Matriarch>,
+  #[serde(default)]
+  pub launch_area: Option<Direction>,
+  #[serde(default)]
+  pub change_direction: Option<ChangeDirection>,
+  #[serde(default)]
+  pub spawner: Option<Spawner>,
+  #[serde(default)]
+  pub constant_velocity: Option<(f32, f32, f32)>,
+  #[serde(default)]
+  pub deadly_area: bool,
+  #[serde(default)]
+  pub exit: bool,
+}
+
+///Everything needed to put a level back exactly how it was
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LevelState {
+  pub mode: SavedMode,
+  pub runtime: f32,
+  pub spawn_stats: SpawnStats,
+  pub entities: Vec<SavedEntity>,
+}
+
+impl LevelState {
+  pub fn path() -> PathBuf {
+    user_data_dir().join(LEVEL_STATE_FILE)
+  }
+
+  pub fn load_user() -> Option<Self> {
+    let path = Self::path();
+    if !path.exists() {
+      return None;
+    }
+
+    match Self::load_no_fallback(&path) {
+      Ok(state) => Some(state),
+      Err(e) => {
+        error!("Failed to load level state {:?}: {}", path, e);
+        None
+      },
+    }
+  }
+
+  pub fn save(&self) {
+    let path = Self::path();
+    if let Err(e) = fs::create_dir_all(user_data_dir()) {
+      error!("Failed to create save directory for {:?}: {}", path, e);
+      return;
+    }
+
+    match self.write(&path) {
+      Ok(_) => info!("Saved level state to {:?}", path),
+      Err(e) => error!("Failed to write level state {:?}: {}", path, e),
+    }
+  }
+}
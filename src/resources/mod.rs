@@ -2,12 +2,32 @@ mod physics;
 mod commands;
 mod spawn_stats;
 mod sound;
+mod sound_events;
+mod audio_settings;
 mod sprites;
 mod level;
+mod simulation;
+mod save_data;
+mod endless;
+mod daily_history;
+mod time_attack;
+mod ghost;
+mod rewind;
+mod level_state;
 
 pub use self::physics::*;
 pub use self::commands::*;
 pub use self::spawn_stats::*;
 pub use self::sound::*;
+pub use self::sound_events::*;
+pub use self::audio_settings::*;
 pub use self::sprites::*;
-pub use self::level::*;
\ No newline at end of file
+pub use self::level::*;
+pub use self::simulation::*;
+pub use self::save_data::*;
+pub use self::endless::*;
+pub use self::daily_history::*;
+pub use self::time_attack::*;
+pub use self::ghost::*;
+pub use self::rewind::*;
+pub use self::level_state::*;
\ No newline at end of file
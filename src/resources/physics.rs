@@ -15,6 +15,7 @@ use nphysics2d::{
   },
   world::World,
   volumetric::Volumetric,
+  math::Velocity,
 };
 
 use nalgebra::{
@@ -52,6 +53,8 @@ pub struct PhysicsWorld {
   pub world: World<FSize>,
   time_accumulator: f32,
   timestep: f32,
+  //How many steps have been taken since the world was created
+  step_count: u64,
   collider_entity_map: HashMap<ColliderHandle, Entity>,
   collider_body_map: HashMap<ColliderHandle, BodyHandle>,
   collider_contacts: HashMap<ColliderHandle, Vec<ColliderHandle>>,
@@ -70,6 +73,7 @@ impl PhysicsWorld {
       world: World::new(),
       time_accumulator: 0.0,
       timestep: 0.0,
+      step_count: 0,
       collider_entity_map: HashMap::new(),
       collider_body_map: HashMap::new(),
       collider_contacts: HashMap::new(),
@@ -89,6 +93,25 @@ impl PhysicsWorld {
     self.time_accumulator / self.timestep
   }
 
+  pub fn step_count(&self) -> u64 {
+    self.step_count
+  }
+
+  ///Position and velocity of a rigid body, None for the ground or a body that doesn't exist
+  pub fn body_state(&self, body_handle: BodyHandle) -> Option<(Isometry2<FSize>, Velocity<FSize>)> {
+    self.world
+      .rigid_body(body_handle)
+      .map(|body| (*body.position(), *body.velocity()))
+  }
+
+  pub fn set_body_state(&mut self, body_handle: BodyHandle, position: &Isometry2<FSize>, velocity: &Velocity<FSize>) {
+    if let Some(body) = self.world.rigid_body_mut(body_handle) {
+      body.set_position(*position);
+      body.set_velocity(*velocity);
+    }
+    self.world.activate_body(body_handle);
+  }
+
   pub fn register_entity(&mut self, entity: Entity, collider_handle: ColliderHandle) {
     debug!("Collider {:?} was associated with entity {:?}", collider_handle, entity);
     self.collider_entity_map.insert(collider_handle, entity);
@@ -181,6 +204,7 @@ impl PhysicsWorld {
   pub fn step(&mut self) -> bool {
     if self.time_accumulator >= self.timestep {
       self.time_accumulator -= self.timestep;
+      self.step_count += 1;
       self.do_step();
       true
     } else {
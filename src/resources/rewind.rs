@@ -0,0 +1,103 @@
+use std::collections::{
+  HashSet,
+  VecDeque,
+};
+
+use amethyst::{
+  core::cgmath::Vector2,
+  ecs::Entity,
+};
+
+use nalgebra::Isometry2;
+
+use nphysics2d::math::Velocity;
+
+use ::components::{
+  Age,
+  Color,
+  Matriarch,
+  Spawner,
+  Walker,
+};
+
+use super::{
+  FSize,
+  SpawnStats,
+};
+
+//How many physics steps between snapshots
+pub const REWIND_SNAPSHOT_STEPS: u64 = 6;
+//About 5 seconds of snapshots at the fixed 60hz timestep
+pub const REWIND_MAX_SNAPSHOTS: usize = 50;
+
+///A creep as it was when the snapshot was taken, enough to recreate it if it's since died
+#[derive(Debug, Clone)]
+pub struct CreepSnapshot {
+  pub entity: Entity,
+  pub position: Isometry2<FSize>,
+  pub velocity: Velocity<FSize>,
+  pub walker: Walker,
+  pub age: Age,
+  pub matriarch: Option<Matriarch>,
+  pub color: Option<Color>,
+}
+
+///Spawners delete themselves once they're done so these need recreating too
+#[derive(Debug, Clone)]
+pub struct SpawnerSnapshot {
+  pub entity: Entity,
+  pub spawner: Spawner,
+  //Pixels
+  pub position: Vector2<f32>,
+  pub size: Vector2<f32>,
+  pub color: Option<Color>,
+}
+
+///Gameplay state at one point in time
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+  pub step: u64,
+  pub runtime: f32,
+  //Length of the ghost recording
+  pub ghost_points: usize,
+  pub spawn_stats: SpawnStats,
+  pub creeps: Vec<CreepSnapshot>,
+  pub spawners: Vec<SpawnerSnapshot>,
+  //Lifts, direction changers and rams that existed. Anything else is newer and gets removed on restore
+  pub abilities: HashSet<Entity>,
+}
+
+///Resource holding the last few seconds of snapshots for the current level. Reset whenever a level is loaded.
+#[derive(Debug, Default)]
+pub struct RewindBuffer {
+  pub snapshots: VecDeque<Snapshot>,
+}
+
+impl RewindBuffer {
+  pub fn push(&mut self, snapshot: Snapshot) {
+    self.snapshots.push_back(snapshot);
+    while self.snapshots.len() > REWIND_MAX_SNAPSHOTS {
+      self.snapshots.pop_front();
+    }
+  }
+
+  pub fn last_step(&self) -> Option<u64> {
+    self.snapshots.back().map(|s| s.step)
+  }
+
+  ///A recreated entity has a new id, older snapshots need to refer to the new one
+  pub fn replace_entity(&mut self, old: Entity, new: Entity) {
+    for snapshot in &mut self.snapshots {
+      for creep in &mut snapshot.creeps {
+        if creep.entity == old {
+          creep.entity = new;
+        }
+      }
+      for spawner in &mut snapshot.spawners {
+        if spawner.entity == old {
+          spawner.entity = new;
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,130 @@
+use std::{
+  fs,
+  path::PathBuf,
+};
+
+use amethyst::{
+  config::Config,
+  utils::application_root_dir,
+};
+
+use dirs;
+
+use super::{
+  SpawnStats,
+  TimeAttackRecords,
+};
+
+const SAVE_DIR: &'static str = "ghgj18";
+const SAVE_FILE: &'static str = "save.ron";
+
+///The result of the best run through a level
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct LevelRecord {
+  pub stats: SpawnStats,
+  //Level::runtime when the level was completed
+  pub clear_time: f32,
+  //Best star rating ever achieved, not necessarily from this run
+  #[serde(default)]
+  pub stars: u32,
+}
+
+impl LevelRecord {
+  //More saved is better, if it's a tie the faster run wins
+  fn beats(&self, other: &LevelRecord) -> bool {
+    if self.stats.saved != other.stats.saved {
+      self.stats.saved > other.stats.saved
+    } else {
+      self.clear_time < other.clear_time
+    }
+  }
+}
+
+///Resource containing everything about the player's progress that survives a restart
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct SaveData {
+  //Highest level index that can be played
+  pub unlocked: usize,
+  //Best result for each level (indexed by level), None if it's never been completed
+  pub levels: Vec<Option<LevelRecord>>,
+  #[serde(default)]
+  pub best_endless_score: u32,
+  #[serde(default)]
+  pub time_attack: TimeAttackRecords,
+}
+
+///Directory per user files (save data, settings) are kept in
+pub fn user_data_dir() -> PathBuf {
+  //Fall back to next to the executable if the platform doesn't have a data dir
+  dirs::data_dir()
+    .unwrap_or_else(|| PathBuf::from(application_root_dir()))
+    .join(SAVE_DIR)
+}
+
+impl SaveData {
+  ///Loads the save file, if there isn't one (or it's broken) the player starts from scratch
+  pub fn load_user() -> Self {
+    let path = Self::path();
+    if !path.exists() {
+      info!("No save file at {:?}, starting fresh", path);
+      return Self::default();
+    }
+
+    match Self::load_no_fallback(&path) {
+      Ok(save_data) => save_data,
+      Err(e) => {
+        error!("Failed to load save file {:?}: {}", path, e);
+        Self::default()
+      },
+    }
+  }
+
+  pub fn save(&self) {
+    let path = Self::path();
+    if let Err(e) = fs::create_dir_all(user_data_dir()) {
+      error!("Failed to create save directory for {:?}: {}", path, e);
+      return;
+    }
+
+    match self.write(&path) {
+      Ok(_) => debug!("Saved to {:?}", path),
+      Err(e) => error!("Failed to write save file {:?}: {}", path, e),
+    }
+  }
+
+  fn path() -> PathBuf {
+    user_data_dir().join(SAVE_FILE)
+  }
+
+  pub fn is_unlocked(&self, level: usize) -> bool {
+    level <= self.unlocked
+  }
+
+  pub fn best(&self, level: usize) -> Option<&LevelRecord> {
+    self.levels.get(level).and_then(|r| r.as_ref())
+  }
+
+  ///Records a completed level, keeping the best result. Unlocking the following level is left to moving on to it.
+  ///Returns true if this was a new best.
+  pub fn record_win(&mut self, level: usize, stats: &SpawnStats, clear_time: f32, stars: u32) -> bool {
+    if self.levels.len() <= level {
+      self.levels.resize(level + 1, None);
+    }
+
+    let best = &mut self.levels[level];
+    let stars = stars.max(best.as_ref().map_or(0, |b| b.stars));
+    let record = LevelRecord {
+      stats: stats.clone(),
+      clear_time,
+      stars,
+    };
+    let new_best = best.as_ref().map_or(true, |b| record.beats(b));
+    if new_best {
+      *best = Some(record);
+    } else if let Some(b) = best.as_mut() {
+      b.stars = stars;
+    }
+
+    new_best
+  }
+}
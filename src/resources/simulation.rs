@@ -0,0 +1,33 @@
+use amethyst::core::timing::Time;
+
+///Resource that controls whether the gameplay simulation is running and how fast.
+//Gameplay systems should take their delta from here rather than straight from Time
+// so pausing and slow motion affect everything consistently while rendering carries on.
+pub struct Simulation {
+  pub paused: bool,
+  //Rewind is held, the Rewind system is restoring snapshots instead of the game moving forward
+  pub rewinding: bool,
+  //Multiplier on the gameplay delta, 0.5 is half speed
+  pub time_scale: f32,
+}
+
+impl Default for Simulation {
+  fn default() -> Self {
+    Self {
+      paused: false,
+      rewinding: false,
+      time_scale: 1.0,
+    }
+  }
+}
+
+impl Simulation {
+  ///The delta the gameplay systems should use this frame
+  pub fn delta(&self, time: &Time) -> f32 {
+    if self.paused || self.rewinding {
+      0.0
+    } else {
+      time.delta_seconds() * self.time_scale
+    }
+  }
+}
@@ -1,3 +1,9 @@
+use std::{
+  collections::HashMap,
+  io::Cursor,
+  path::Path,
+};
+
 use amethyst::{
   assets::{
     AssetStorage,
@@ -5,105 +11,292 @@ use amethyst::{
     ProgressCounter,
   },
   audio::{
-    output::Output,
     Source,
     SourceHandle,
+    FlacFormat,
     OggFormat,
     Mp3Format,
+    WavFormat,
   },
   utils::application_root_dir,
 };
 
-use ::config::SoundConfig;
+use rand::{
+  thread_rng,
+  Rng,
+};
+
+use rodio::{
+  self,
+  Decoder,
+  Device,
+  Sink,
+  Source as RodioSource,
+  source::{
+    Buffered,
+    ChannelVolume,
+  },
+};
+
+use ::config::{
+  LevelsConfig,
+  SoundConfig,
+};
 
 const SOUND_PATH: &'static str = "assets/sound";
-const SPAWN_FILE: &'static str = "spawn.ogg";
-const EXIT_FILE: &'static str = "exit.ogg";
-const LIFT_FILE: &'static str = "lift.ogg";
-const DEATH_FILE: &'static str = "death.mp3";
-const EXODUS_FILE: &'static str = "woo.ogg";
-
-const SPAWN_VOLUME: f32 = 0.8;
-const EXIT_VOLUME: f32 = 0.6;
-const LIFT_VOLUME: f32 = 0.6;
-const DEATH_VOLUME: f32 = 1.0;
-const EXODUS_VOLUME: f32 = 0.2;
-
-///Resource containing the sound effects the game uses.
+//Pitch variation has to stay below 1 or the speed could end up 0 or negative
+const MAX_PITCH_VARIATION: f32 = 0.95;
+
+type DecodedSound = Buffered<Decoder<Cursor<Source>>>;
+
+//One entry from the sound table with its files loaded
+struct SoundEffect {
+  variations: Vec<SourceHandle>,
+  //Each variation is decoded the first time it's played, after that every play clones the buffered samples
+  decoded: Vec<Option<DecodedSound>>,
+  volume: f32,
+  pitch_variation: f32,
+  cooldown: f32,
+  priority: u32,
+}
+
+//A sound effect that's currently playing
+struct Voice {
+  sink: Sink,
+  priority: u32,
+}
+
+///Resource containing the sound effects and music the game uses.
 pub struct Sounds {
+  //Master volume
   pub volume: f32,
-  spawn: SourceHandle,
-  exit: SourceHandle,
-  lift: SourceHandle,
-  death: SourceHandle,
-  exodus: SourceHandle,
-}
+  pub music_volume: f32,
+  pub sfx_volume: f32,
+  pub muted: bool,
+
+  //Effects are played straight through rodio since amethyst's Output can't change the pitch.
+  //Output doesn't hand out its device either, so this is a second stream on the default device that the OS mixes with the music.
+  device: Option<Device>,
+  effects: HashMap<String, SoundEffect>,
+  voices: Vec<Voice>,
+  max_voices: usize,
+  //When each effect was last played (seconds, absolute time)
+  last_played: HashMap<String, f64>,
 
-fn load_ogg_file(loader: &Loader, storage: &AssetStorage<Source>, progress: &mut ProgressCounter, file: &str) -> SourceHandle {
-  loader
-    .load(
-      file,
-      OggFormat,
-      (),
-      progress,
-      storage)
+  music: HashMap<String, SourceHandle>,
+  default_playlist: Vec<String>,
+  playlist: Vec<String>,
+  //Tracks still to play from the playlist, next one is at the end
+  queue: Vec<String>,
+  shuffle: bool,
+  repeat: bool,
 }
 
-fn load_mp3_file(loader: &Loader, storage: &AssetStorage<Source>, progress: &mut ProgressCounter, file: &str) -> SourceHandle {
-  loader
-    .load(
-      file,
-      Mp3Format,
-      (),
-      progress,
-      storage)
+//The format is picked from the extension
+fn load_sound_file(loader: &Loader, storage: &AssetStorage<Source>, progress: &mut ProgressCounter, file: &str) -> Option<SourceHandle> {
+  let path = format!("{}/{}/{}", application_root_dir(), SOUND_PATH, file);
+  match Path::new(file).extension().and_then(|e| e.to_str()).map(|e| e.to_lowercase()).as_ref().map(|e| e.as_str()) {
+    Some("ogg") => Some(loader.load(path, OggFormat, (), progress, storage)),
+    Some("mp3") => Some(loader.load(path, Mp3Format, (), progress, storage)),
+    Some("wav") => Some(loader.load(path, WavFormat, (), progress, storage)),
+    Some("flac") => Some(loader.load(path, FlacFormat, (), progress, storage)),
+    _ => {
+      error!("Unknown sound format for {}, expected ogg, mp3, wav or flac", file);
+      None
+    },
+  }
 }
 
 impl Sounds {
-  pub fn new(loader: &Loader, storage: &AssetStorage<Source>, progress: &mut ProgressCounter, sound_config: &SoundConfig) -> Self {
-    let root_dir = application_root_dir();
-    Sounds {
-      volume: sound_config.volume,
-      spawn: load_ogg_file(loader, storage, progress, &format!("{}/{}/{}", root_dir, SOUND_PATH, SPAWN_FILE)),
-      exit: load_ogg_file(loader, storage, progress, &format!("{}/{}/{}", root_dir, SOUND_PATH, EXIT_FILE)),
-      lift: load_ogg_file(loader, storage, progress, &format!("{}/{}/{}", root_dir, SOUND_PATH, LIFT_FILE)),
-      death: load_mp3_file(loader, storage, progress, &format!("{}/{}/{}", root_dir, SOUND_PATH, DEATH_FILE)),
-      exodus: load_ogg_file(loader, storage, progress, &format!("{}/{}/{}", root_dir, SOUND_PATH, EXODUS_FILE)),
+  pub fn new(loader: &Loader, storage: &AssetStorage<Source>, progress: &mut ProgressCounter, sound_config: &SoundConfig, levels_config: &LevelsConfig) -> Self {
+    let mut effects = HashMap::new();
+    for (id, effect) in &sound_config.effects {
+      let variations: Vec<SourceHandle> = effect.files
+        .iter()
+        .filter_map(|file| load_sound_file(loader, storage, progress, file))
+        .collect();
+      if variations.is_empty() {
+        warn!("Sound {} has no files that can be loaded", id);
+        continue;
+      }
+
+      let pitch_variation = effect.pitch_variation.max(0.0).min(MAX_PITCH_VARIATION);
+      if pitch_variation != effect.pitch_variation {
+        warn!("Sound {} has pitch_variation {}, it has to be from 0 to below 1. Using {}", id, effect.pitch_variation, pitch_variation);
+      }
+
+      effects.insert(id.clone(), SoundEffect {
+        decoded: vec![None; variations.len()],
+        variations,
+        volume: effect.volume,
+        pitch_variation,
+        cooldown: effect.cooldown,
+        priority: effect.priority,
+      });
     }
-  }
 
-  //This is called by the DJ system when the previous music track ends
-  pub fn next_music(&mut self) -> Option<SourceHandle> {
-    None
+    //Everything that could be played is loaded up front so changing level doesn't have to wait
+    let mut music = HashMap::new();
+    let level_tracks = levels_config.levels
+      .iter()
+      .filter_map(|level| level.music.as_ref())
+      .flat_map(|tracks| tracks.iter());
+    for track in sound_config.music.tracks.iter().chain(level_tracks) {
+      if !music.contains_key(track) {
+        if let Some(handle) = load_sound_file(loader, storage, progress, track) {
+          music.insert(track.clone(), handle);
+        }
+      }
+    }
+
+    let device = rodio::default_output_device();
+    if device.is_none() {
+      warn!("No audio output device, sound effects are disabled");
+    }
+
+    let mut sounds = Sounds {
+      volume: sound_config.volume,
+      music_volume: sound_config.music_volume,
+      sfx_volume: sound_config.sfx_volume,
+      muted: false,
+      device,
+      effects,
+      voices: Vec::new(),
+      max_voices: sound_config.max_voices,
+      last_played: HashMap::new(),
+      music,
+      default_playlist: sound_config.music.tracks.clone(),
+      playlist: Vec::new(),
+      queue: Vec::new(),
+      shuffle: sound_config.music.shuffle,
+      repeat: sound_config.music.repeat,
+    };
+    let default_playlist = sounds.default_playlist.clone();
+    sounds.set_playlist(Some(&default_playlist));
+    sounds
   }
 
-  pub fn play_spawn(&self, storage: &AssetStorage<Source>, output: &Output) {
-    if let Some(sound) = storage.get(&self.spawn) {
-      output.play_once(sound, self.volume * SPAWN_VOLUME);
+  ///Switches to the given tracks, or the default playlist if there aren't any.
+  ///Returns true if that's different to what was playing.
+  pub fn set_playlist(&mut self, tracks: Option<&Vec<String>>) -> bool {
+    let tracks = tracks.unwrap_or(&self.default_playlist).clone();
+    if tracks == self.playlist {
+      return false;
     }
+
+    self.playlist = tracks;
+    self.queue.clear();
+    self.fill_queue();
+    true
   }
 
-  pub fn play_exit(&self, storage: &AssetStorage<Source>, output: &Output) {
-    if let Some(sound) = storage.get(&self.exit) {
-      output.play_once(sound, self.volume * EXIT_VOLUME);
+  fn fill_queue(&mut self) {
+    self.queue = self.playlist.iter().rev().cloned().collect();
+    if self.shuffle {
+      thread_rng().shuffle(&mut self.queue);
     }
   }
 
-  pub fn play_lift(&self, storage: &AssetStorage<Source>, output: &Output) {
-    if let Some(sound) = storage.get(&self.lift) {
-      output.play_once(sound, self.volume * LIFT_VOLUME);
+  ///The music volume after the master volume and mute are applied
+  pub fn music_volume(&self) -> f32 {
+    if self.muted {
+      0.0
+    } else {
+      self.volume * self.music_volume
     }
   }
 
-  pub fn play_death(&self, storage: &AssetStorage<Source>, output: &Output) {
-    if let Some(sound) = storage.get(&self.death) {
-      output.play_once(sound, self.volume * DEATH_VOLUME);
+  //This is called by the DJ system when the previous music track ends
+  pub fn next_music(&mut self) -> Option<SourceHandle> {
+    if self.queue.is_empty() && self.repeat {
+      self.fill_queue();
     }
+
+    self.queue
+      .pop()
+      .and_then(|track| self.music.get(&track).cloned())
   }
 
-  pub fn play_exodus(&self, storage: &AssetStorage<Source>, output: &Output) {
-    if let Some(sound) = storage.get(&self.exodus) {
-      output.play_once(sound, self.volume * EXODUS_VOLUME);
+  ///Priority of the sound with the given id, unknown sounds have the lowest
+  pub fn priority(&self, id: &str) -> u32 {
+    self.effects.get(id).map_or(0, |effect| effect.priority)
+  }
+
+  ///Plays the sound with the given id from the sound table. A random file is picked if it has more than one.
+  ///Nothing is played if it's still cooling down or every voice is taken by something at least as important.
+  ///Pan goes from -1 (left) to 1 (right).
+  ///This should only be called by the SoundDispatcher, everything else sends a SoundEvent.
+  pub fn play(&mut self, id: &str, volume_scale: f32, pan: f32, now: f64, storage: &AssetStorage<Source>) {
+    let device = match self.device {
+      Some(ref device) if !self.muted => device,
+      _ => return,
+    };
+    let effect = match self.effects.get_mut(id) {
+      Some(effect) => effect,
+      None => {
+        debug!("No sound with id {}", id);
+        return;
+      },
+    };
+
+    if let Some(last) = self.last_played.get(id) {
+      if now - last < effect.cooldown as f64 {
+        return;
+      }
+    }
+
+    self.voices.retain(|voice| !voice.sink.empty());
+    if self.voices.len() >= self.max_voices {
+      let lowest = self.voices
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, voice)| voice.priority)
+        .map(|(i, voice)| (i, voice.priority));
+      match lowest {
+        Some((i, priority)) if priority < effect.priority => self.voices.remove(i).sink.stop(),
+        _ => return,
+      }
+    }
+
+    let mut rng = thread_rng();
+    let variation = rng.gen_range(0, effect.variations.len());
+    if effect.decoded[variation].is_none() {
+      let source = match storage.get(&effect.variations[variation]) {
+        Some(source) => source,
+        //Still loading
+        None => return,
+      };
+      match Decoder::new(Cursor::new(source.clone())) {
+        Ok(decoder) => effect.decoded[variation] = Some(decoder.buffered()),
+        Err(e) => {
+          error!("Failed to decode sound {}: {}", id, e);
+          return;
+        },
+      }
+    }
+    let pitch = if effect.pitch_variation > 0.0 {
+      1.0 + rng.gen_range(-effect.pitch_variation, effect.pitch_variation)
+    } else {
+      1.0
+    };
+
+    //The near side stays at full volume, the far side fades out
+    let pan = pan.max(-1.0).min(1.0);
+    let channel_volumes = vec![(1.0 - pan).min(1.0), (1.0 + pan).min(1.0)];
+
+    if let Some(ref decoded) = effect.decoded[variation] {
+      let sink = Sink::new(device);
+      let source = decoded
+        .clone()
+        .speed(pitch)
+        .amplify(self.volume * self.sfx_volume * effect.volume * volume_scale)
+        .convert_samples::<f32>();
+      sink.append(ChannelVolume::new(source, channel_volumes));
+      self.voices.push(Voice {
+        sink,
+        priority: effect.priority,
+      });
+      self.last_played.insert(id.to_string(), now);
     }
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,32 @@
+use amethyst::{
+  core::cgmath::Vector2,
+  shrev::EventChannel,
+};
+
+///Request to play a sound from the sound table. Systems send these rather than playing sounds
+///themselves so the SoundDispatcher can throttle and combine them.
+#[derive(Debug, Clone)]
+pub struct SoundEvent {
+  pub id: String,
+  //Where in the world it happened, used for panning and attenuation. None plays it centered at full volume
+  pub position: Option<Vector2<f32>>,
+}
+
+impl SoundEvent {
+  pub fn new(id: &str) -> Self {
+    Self {
+      id: id.to_string(),
+      position: None,
+    }
+  }
+
+  pub fn at(id: &str, position: Vector2<f32>) -> Self {
+    Self {
+      id: id.to_string(),
+      position: Some(position),
+    }
+  }
+}
+
+///This is the channel resource sound events get sent to
+pub type SoundChannel = EventChannel<SoundEvent>;
@@ -1,11 +1,14 @@
 ///Resource that tracks how many creeps have been spawned/died/saved/etc.
-#[derive(Default)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize)]
 pub struct SpawnStats {
   pub total: u32,
   pub spawned: u32,
   pub killed: u32,
   pub saved: u32,
   pub win_ratio: f32,
+  //How many abilities (lift, change direction, ram) the player has used
+  #[serde(default)]
+  pub abilities_used: u32,
 }
 
 impl SpawnStats {
@@ -16,4 +19,14 @@ impl SpawnStats {
       self.saved as f32 / self.total as f32
     }
   }
-}
\ No newline at end of file
+
+  ///Creeps that haven't been saved or killed yet (including ones still to spawn)
+  pub fn remaining(&self) -> u32 {
+    self.total.saturating_sub(self.saved + self.killed)
+  }
+
+  ///Creeps that are currently walking around
+  pub fn alive(&self) -> u32 {
+    self.spawned.saturating_sub(self.saved + self.killed)
+  }
+}
@@ -1,4 +1,11 @@
+use std::{
+  collections::HashMap,
+  fs::File,
+  io::Read as IoRead,
+};
+
 use amethyst::{
+  config::Config,
   ecs::prelude::*,
 
   assets::{
@@ -13,6 +20,7 @@ use amethyst::{
     Sprite,
     SpriteRender,
     SpriteSheet,
+    SpriteSheetHandle,
     TextureCoordinates,
     TextureMetadata,
     Texture,
@@ -20,119 +28,322 @@ use amethyst::{
   utils::application_root_dir,
 };
 
-use ::config::SpritesConfig;
+use ::{
+  components::SpriteAnimation,
+  config::{
+    AnimationConfig,
+    AtlasConfig,
+    AtlasLayout,
+    LoopMode,
+    SpriteConfig,
+    SpritesConfig,
+  },
+};
 
 const TEXTURE_PATH: &'static str = "assets/texture";
 const SPRITESHEET_FILE: &'static str = "spritesheet.png";
 
-const LIFT_SPRITE_NAME: &'static str = "lift";
-const CHANGE_DIRECTION_SPRITE_NAME: &'static str = "change_direction";
+///Names of the sprites the game itself uses, anything else in the config is only there for levels or ui to use
+pub const LIFT_SPRITE: &'static str = "lift";
+pub const CHANGE_DIRECTION_SPRITE: &'static str = "change_direction";
+pub const GHOST_SPRITE: &'static str = "ghost";
+const REQUIRED_SPRITES: [&'static str; 3] = [LIFT_SPRITE, CHANGE_DIRECTION_SPRITE, GHOST_SPRITE];
 
-// `texture_id` is a application defined ID given to the texture to store in the `World`.
-// This is needed to link the texture to the sprite_sheet.
-const TEXTURE_ID: u64 = 0;
+///Creep animations, creeps are drawn as plain cubes unless there's a walk animation
+pub const CREEP_WALK_ANIMATION: &'static str = "creep_walk";
+pub const CREEP_FALL_ANIMATION: &'static str = "creep_fall";
+pub const CREEP_LAUNCH_ANIMATION: &'static str = "creep_launch";
+///The matriarch's versions, creeps ones are used for any that are missing
+pub const MATRIARCH_WALK_ANIMATION: &'static str = "matriarch_walk";
+pub const MATRIARCH_FALL_ANIMATION: &'static str = "matriarch_fall";
+pub const MATRIARCH_LAUNCH_ANIMATION: &'static str = "matriarch_launch";
+
+//One texture and the sprites cut out of it
+struct Sheet {
+  texture: String,
+  width: u32,
+  height: u32,
+  sprites: Vec<SpriteConfig>,
+}
+
+struct Frame {
+  sprite_sheet: SpriteSheetHandle,
+  sprite_number: usize,
+  duration: f32,
+}
+
+///Frames of an animation with the names resolved to sprites
+pub struct Animation {
+  frames: Vec<Frame>,
+  mode: LoopMode,
+  duration: f32,
+}
+
+impl Animation {
+  ///The sprite to show once the animation has been playing for elapsed seconds
+  pub fn frame_at(&self, elapsed: f32) -> (&SpriteSheetHandle, usize) {
+    let t = if self.duration <= 0.0 {
+      0.0
+    } else {
+      match self.mode {
+        LoopMode::Loop => elapsed % self.duration,
+        LoopMode::Once => elapsed,
+        LoopMode::PingPong => {
+          let t = elapsed % (self.duration * 2.0);
+          if t > self.duration { self.duration * 2.0 - t } else { t }
+        },
+      }
+    };
+
+    let mut end = 0.0;
+    for frame in &self.frames {
+      end += frame.duration;
+      if t < end {
+        return (&frame.sprite_sheet, frame.sprite_number);
+      }
+    }
+    let last = &self.frames[self.frames.len() - 1];
+    (&last.sprite_sheet, last.sprite_number)
+  }
+}
 
-///Resource that contains templates for the sprites the game uses.
+///Resource containing a template SpriteRender for every sprite in SpritesConfig and its atlases, looked up by name.
+///Also has the animations from the config.
 pub struct Sprites {
-  pub lift: SpriteRender,
-  pub change_direction: SpriteRender,
+  by_name: HashMap<String, SpriteRender>,
+  animations: HashMap<String, Animation>,
 }
 
-fn find_and_load_named_sprite(name: &str, sprites_config: &SpritesConfig) -> Sprite {
-  let spritesheet_height = sprites_config.sheet_height as f32;
-  let spritesheet_width = sprites_config.sheet_width as f32;
-  for sc in &sprites_config.sprites {
-    if sc.name == name {
-      let w = sc.sheet_width as f32;
-      let h = sc.sheet_height as f32;
-      let x = sc.sheet_x as f32;
-      let y = sc.sheet_y as f32;
-
-      let tex_coords = TextureCoordinates {
-        left: x / spritesheet_width,
-        right: (x + w) / spritesheet_width,
-        bottom: 1.0 - y / spritesheet_height,
-        top: 1.0 - (y + h) / spritesheet_height,
-      };
+//The rect has to be inside the sheet and not empty
+fn validate_rect(sc: &SpriteConfig, sheet: &Sheet) -> bool {
+  if sc.sheet_width == 0 || sc.sheet_height == 0 {
+    error!("Sprite {} in {} has no size", sc.name, sheet.texture);
+    return false;
+  }
+  if sc.sheet_x + sc.sheet_width > sheet.width || sc.sheet_y + sc.sheet_height > sheet.height {
+    error!("Sprite {} ({}, {}, {}x{}) is outside {} ({}x{})",
+      sc.name, sc.sheet_x, sc.sheet_y, sc.sheet_width, sc.sheet_height,
+      sheet.texture, sheet.width, sheet.height);
+    return false;
+  }
+  true
+}
 
-      let w = sc.scaled_width as f32;
-      let h = sc.scaled_height as f32;
-      let x = sc.scaled_x as f32;
-      let y = sc.scaled_y as f32;
+//Rects in the config are in pixels from the top left of the image, texture coordinates go up from the bottom
+fn load_sprite(sc: &SpriteConfig, sheet: &Sheet) -> Sprite {
+  let spritesheet_height = sheet.height as f32;
+  let spritesheet_width = sheet.width as f32;
 
-      return Sprite {
-        width: w,
-        height: h,
-        offsets: [x, y],
-        tex_coords: tex_coords,
-      };
+  let w = sc.sheet_width as f32;
+  let h = sc.sheet_height as f32;
+  let x = sc.sheet_x as f32;
+  let y = sc.sheet_y as f32;
+
+  let tex_coords = TextureCoordinates {
+    left: x / spritesheet_width,
+    right: (x + w) / spritesheet_width,
+    top: 1.0 - y / spritesheet_height,
+    bottom: 1.0 - (y + h) / spritesheet_height,
+  };
+
+  let w = sc.scaled_width as f32;
+  let h = sc.scaled_height as f32;
+  let x = sc.scaled_x as f32;
+  let y = sc.scaled_y as f32;
+
+  Sprite {
+    width: w,
+    height: h,
+    offsets: [x, y],
+    tex_coords: tex_coords,
+  }
+}
+
+//Reads the size from the png header so a sheet with the wrong size in the config gets noticed
+fn png_size(path: &str) -> Option<(u32, u32)> {
+  let mut header = [0u8; 24];
+  File::open(path).and_then(|mut f| f.read_exact(&mut header)).ok()?;
+  let read_u32 = |b: &[u8]| (b[0] as u32) << 24 | (b[1] as u32) << 16 | (b[2] as u32) << 8 | b[3] as u32;
+  Some((read_u32(&header[16..20]), read_u32(&header[20..24])))
+}
+
+fn load_atlas(file: &str) -> Option<Sheet> {
+  let path = format!("{}/{}/{}", application_root_dir(), TEXTURE_PATH, file);
+  let atlas = match AtlasConfig::load_no_fallback(&path) {
+    Ok(atlas) => atlas,
+    Err(e) => {
+      error!("Failed to load sprite atlas {}: {}", path, e);
+      return None;
+    },
+  };
+
+  let sprites = match atlas.layout {
+    AtlasLayout::Grid(ref grid) => grid.sprites(atlas.width),
+    AtlasLayout::Packed(ref sprites) => sprites.clone(),
+  };
+
+  Some(Sheet {
+    texture: atlas.texture,
+    width: atlas.width,
+    height: atlas.height,
+    sprites,
+  })
+}
+
+fn load_animation(ac: &AnimationConfig, by_name: &HashMap<String, SpriteRender>) -> Option<Animation> {
+  let mut frames = Vec::new();
+  for frame in &ac.frames {
+    match by_name.get(&frame.sprite) {
+      Some(render) => frames.push(Frame {
+        sprite_sheet: render.sprite_sheet.clone(),
+        sprite_number: render.sprite_number,
+        duration: frame.duration.max(0.0),
+      }),
+      None => {
+        error!("Animation {} uses sprite {} which doesn't exist", ac.name, frame.sprite);
+        return None;
+      },
     }
   }
-  panic!("Failed to find sprite named {}", name);
+  if frames.is_empty() {
+    error!("Animation {} has no frames", ac.name);
+    return None;
+  }
+
+  Some(Animation {
+    duration: frames.iter().map(|f| f.duration).sum(),
+    frames,
+    mode: ac.mode,
+  })
 }
 
 impl Sprites {
   pub fn new(world: &mut World, progress: &mut ProgressCounter) -> Self {
-    {
-      let root_dir = application_root_dir();
-
-      let loader = world.read_resource::<Loader>();
-      let storage = world.read_resource::<AssetStorage<Texture>>();
-      let texture_handle = loader.load(
-        format!("{}/{}/{}", root_dir, TEXTURE_PATH, SPRITESHEET_FILE),
-        PngFormat,
-        TextureMetadata::srgb_scale(),
-        progress,
-        &storage,
-      );
-
-      let mut material_texture_set = world.write_resource::<MaterialTextureSet>();
-      material_texture_set.insert(TEXTURE_ID, texture_handle);
-    }
-
-    let (lift_sprite, cd_sprite) = {
+    //The sheet in the game config comes first, then the atlases in the order they're listed
+    let sheets = {
       let sprites_config = world.read_resource::<SpritesConfig>();
-      let lift_sprite = find_and_load_named_sprite(LIFT_SPRITE_NAME, &sprites_config);
-      let cd_sprite = find_and_load_named_sprite(CHANGE_DIRECTION_SPRITE_NAME, &sprites_config);
-      (lift_sprite, cd_sprite)
+      let mut sheets = vec![Sheet {
+        texture: SPRITESHEET_FILE.to_string(),
+        width: sprites_config.sheet_width,
+        height: sprites_config.sheet_height,
+        sprites: sprites_config.sprites.clone(),
+      }];
+      sheets.extend(sprites_config.atlases.iter().filter_map(|file| load_atlas(file)));
+      sheets
     };
 
-    let sprite_sheet = SpriteSheet {
-      texture_id: TEXTURE_ID,
-      sprites: vec![lift_sprite, cd_sprite],
-    };
+    let mut by_name = HashMap::new();
+    // `texture_id` is a application defined ID given to the texture to store in the `World`.
+    // This is needed to link the texture to the sprite_sheet. Each sheet uses its index.
+    for (texture_id, sheet) in sheets.iter().enumerate() {
+      let texture_id = texture_id as u64;
+      let path = format!("{}/{}/{}", application_root_dir(), TEXTURE_PATH, sheet.texture);
 
-    let sprite_count = sprite_sheet.sprites.len();
+      if let Some((width, height)) = png_size(&path) {
+        if (width, height) != (sheet.width, sheet.height) {
+          warn!("{} is {}x{} but the config says {}x{}, sprites will be in the wrong place",
+            sheet.texture, width, height, sheet.width, sheet.height);
+        }
+      }
 
-    let sprite_sheet_handle = {
-      let loader = world.read_resource::<Loader>();
-      let sprite_sheet_store = world.read_resource::<AssetStorage<SpriteSheet>>();
-      loader.load_from_data(sprite_sheet, (), &sprite_sheet_store)
-    };
+      {
+        let loader = world.read_resource::<Loader>();
+        let storage = world.read_resource::<AssetStorage<Texture>>();
+        let texture_handle = loader.load(
+          path,
+          PngFormat,
+          TextureMetadata::srgb_scale(),
+          &mut *progress,
+          &storage,
+        );
 
-    let lift_render = SpriteRender {
-      sprite_sheet: sprite_sheet_handle.clone(),
-      sprite_number: 0,
-      flip_horizontal: false,
-      flip_vertical: true, //TODO: This shouldn't be necessary, mistake in tex_coords maybe?
-    };
+        let mut material_texture_set = world.write_resource::<MaterialTextureSet>();
+        material_texture_set.insert(texture_id, texture_handle);
+      }
 
-    let cd_render = SpriteRender {
-      sprite_sheet: sprite_sheet_handle.clone(),
-      sprite_number: 1,
-      flip_horizontal: false,
-      flip_vertical: false,
-    };
+      //Sprite numbers are the index in the sheet once bad rects are left out
+      let valid: Vec<&SpriteConfig> = sheet.sprites
+        .iter()
+        .filter(|sc| validate_rect(sc, sheet))
+        .collect();
 
+      let sprite_sheet = SpriteSheet {
+        texture_id,
+        sprites: valid.iter().map(|sc| load_sprite(sc, sheet)).collect(),
+      };
 
-    //These are just to check for typos in sprite_number values above
-    //TODO: a better way of registering sprites and tracking their number
-    assert!(lift_render.sprite_number < sprite_count);
-    assert!(cd_render.sprite_number < sprite_count);
+      let sprite_sheet_handle = {
+        let loader = world.read_resource::<Loader>();
+        let sprite_sheet_store = world.read_resource::<AssetStorage<SpriteSheet>>();
+        loader.load_from_data(sprite_sheet, (), &sprite_sheet_store)
+      };
+
+      for (sprite_number, sc) in valid.into_iter().enumerate() {
+        if by_name.contains_key(&sc.name) {
+          warn!("Sprite {} is defined more than once, only the first is used", sc.name);
+          continue;
+        }
+        by_name.insert(sc.name.clone(), SpriteRender {
+          sprite_sheet: sprite_sheet_handle.clone(),
+          sprite_number,
+          flip_horizontal: sc.flip_horizontal,
+          flip_vertical: sc.flip_vertical,
+        });
+      }
+    }
+
+    for name in REQUIRED_SPRITES.iter() {
+      if !by_name.contains_key(*name) {
+        error!("No sprite named {} in the sprites config", name);
+      }
+    }
+
+    let mut animations = HashMap::new();
+    for ac in &world.read_resource::<SpritesConfig>().animations {
+      if animations.contains_key(&ac.name) {
+        warn!("Animation {} is defined more than once, only the first is used", ac.name);
+        continue;
+      }
+      if let Some(animation) = load_animation(ac, &by_name) {
+        animations.insert(ac.name.clone(), animation);
+      }
+    }
 
     Sprites {
-      lift: lift_render,
-      change_direction: cd_render,
+      by_name,
+      animations,
+    }
+  }
+
+  ///A copy of the named sprite ready to be added to an entity, None if there isn't one by that name
+  pub fn get(&self, name: &str) -> Option<SpriteRender> {
+    self.by_name.get(name).cloned()
+  }
+
+  pub fn animation(&self, name: &str) -> Option<&Animation> {
+    self.animations.get(name)
+  }
+
+  ///A SpriteAnimation component for the named animation, None if there isn't one by that name.
+  ///Anything drawn with a sprite that has an animation of the same name should be given this too.
+  pub fn animate(&self, name: &str) -> Option<SpriteAnimation> {
+    if self.animations.contains_key(name) {
+      Some(SpriteAnimation::new(name))
+    } else {
+      None
     }
   }
-}
\ No newline at end of file
+
+  ///A SpriteRender showing the first frame of the named animation
+  pub fn first_frame(&self, name: &str) -> Option<SpriteRender> {
+    self.animations.get(name).map(|animation| {
+      let (sprite_sheet, sprite_number) = animation.frame_at(0.0);
+      SpriteRender {
+        sprite_sheet: sprite_sheet.clone(),
+        sprite_number,
+        flip_horizontal: false,
+        flip_vertical: false,
+      }
+    })
+  }
+}
@@ -0,0 +1,89 @@
+///Resource tracking the current time attack run. A run goes through every level in order against the clock.
+#[derive(Debug, Default)]
+pub struct TimeAttack {
+  pub active: bool,
+  //Total time since the run started, keeps going through restarts
+  pub run_time: f32,
+  //run_time at the end of each level completed so far (indexed by level)
+  pub splits: Vec<f32>,
+  //Set once the last level is done so the clock stops
+  pub finished: bool,
+}
+
+impl TimeAttack {
+  pub fn start(&mut self) {
+    *self = Self {
+      active: true,
+      ..Self::default()
+    };
+  }
+
+  pub fn stop(&mut self) {
+    self.active = false;
+  }
+
+  ///Records the end of a level, replaces the split if the level was replayed
+  pub fn split(&mut self, level: usize) {
+    self.splits.truncate(level);
+    self.splits.push(self.run_time);
+  }
+
+  ///Difference between the latest split and the same split in the best run (negative is ahead)
+  pub fn last_delta(&self, records: &TimeAttackRecords) -> Option<f32> {
+    let index = self.splits.len().checked_sub(1)?;
+    records.run_splits
+      .get(index)
+      .map(|best| self.splits[index] - best)
+  }
+
+  ///Difference between the clock now and where the best run was when it finished the given level (negative is ahead).
+  ///Goes up while the level is being played so it shows how much is left before falling behind.
+  pub fn live_delta(&self, level: usize, records: &TimeAttackRecords) -> Option<f32> {
+    records.run_splits
+      .get(level)
+      .map(|best| self.run_time - best)
+  }
+}
+
+///Personal bests for time attack, saved along with the rest of the progress
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct TimeAttackRecords {
+  //Fastest time for each level (indexed by level)
+  pub level_best: Vec<Option<f32>>,
+  //Splits from the fastest full run, empty if no run has been finished
+  pub run_splits: Vec<f32>,
+}
+
+impl TimeAttackRecords {
+  pub fn best_level(&self, level: usize) -> Option<f32> {
+    self.level_best.get(level).and_then(|t| *t)
+  }
+
+  pub fn best_run(&self) -> Option<f32> {
+    self.run_splits.last().cloned()
+  }
+
+  pub fn record_level(&mut self, level: usize, time: f32) {
+    if self.level_best.len() <= level {
+      self.level_best.resize(level + 1, None);
+    }
+    let best = &mut self.level_best[level];
+    if best.map_or(true, |b| time < b) {
+      *best = Some(time);
+    }
+  }
+
+  ///Keeps the splits if they make a faster run than the current best. Returns true if it was a new best.
+  pub fn record_run(&mut self, splits: &[f32]) -> bool {
+    let time = match splits.last() {
+      Some(time) => *time,
+      None => return false,
+    };
+    if self.best_run().map_or(true, |b| time < b) {
+      self.run_splits = splits.to_vec();
+      true
+    } else {
+      false
+    }
+  }
+}
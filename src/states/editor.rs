@@ -0,0 +1,463 @@
+use std::fs;
+
+use amethyst::{
+  prelude::*,
+  controls::FlyControlTag,
+  core::{
+    cgmath::Vector3,
+    transform::components::Transform,
+  },
+  ecs::prelude::*,
+  input::is_key_down,
+  renderer::{
+    DebugLinesComponent,
+    DebugLinesParams,
+  },
+  winit::VirtualKeyCode,
+};
+
+use ::{
+  components::Color,
+  config::{
+    CameraConfig,
+    Cuboid,
+    CuboidSet,
+    LevelConfig,
+    game_config_path,
+  },
+  generation::{
+    level_to_ron,
+    replace_level_ron,
+  },
+  resources::{
+    Command,
+    CommandChannel,
+    GameMode,
+    Level,
+    LoadState,
+  },
+};
+
+use super::{
+  loading::grid_lines,
+  menu::{
+    create_ui,
+    remove_ui,
+    set_text,
+  },
+};
+
+//A quarter of the gridline spacing
+const SNAP: f32 = 16.0;
+const ROTATION_STEP: f32 = 0.2617994; //15 degrees
+const MIN_SIZE: f32 = 2.0;
+const HIGHLIGHT_COLOR: [f32; 4] = [1.0, 1.0, 0.0, 1.0];
+//None uses the set's color
+const PALETTE: [Option<[f32; 4]>; 7] = [
+  None,
+  Some([0.5, 0.5, 0.5, 1.0]),
+  Some([0.8, 0.1, 0.1, 1.0]),
+  Some([0.1, 0.8, 0.1, 1.0]),
+  Some([0.1, 0.1, 0.8, 1.0]),
+  Some([0.8, 0.8, 0.1, 1.0]),
+  Some([0.9, 0.9, 0.9, 1.0]),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ObjectSet {
+  Walls,
+  DeadlyAreas,
+  Exits,
+  Spawners,
+  Blocks,
+}
+
+impl ObjectSet {
+  fn name(&self) -> &'static str {
+    match self {
+      ObjectSet::Walls => "WALLS",
+      ObjectSet::DeadlyAreas => "DEADLY AREAS",
+      ObjectSet::Exits => "EXITS",
+      ObjectSet::Spawners => "SPAWNERS",
+      ObjectSet::Blocks => "BLOCKS",
+    }
+  }
+
+  fn get<'a>(&self, level: &'a LevelConfig) -> &'a Option<CuboidSet> {
+    match self {
+      ObjectSet::Walls => &level.walls,
+      ObjectSet::DeadlyAreas => &level.deadly_areas,
+      ObjectSet::Exits => &level.exits,
+      ObjectSet::Spawners => &level.spawners,
+      ObjectSet::Blocks => &level.blocks,
+    }
+  }
+
+  fn get_mut<'a>(&self, level: &'a mut LevelConfig) -> &'a mut Option<CuboidSet> {
+    match self {
+      ObjectSet::Walls => &mut level.walls,
+      ObjectSet::DeadlyAreas => &mut level.deadly_areas,
+      ObjectSet::Exits => &mut level.exits,
+      ObjectSet::Spawners => &mut level.spawners,
+      ObjectSet::Blocks => &mut level.blocks,
+    }
+  }
+
+  //Walls are created with the rotation as is, everything else gets it flipped (see PhysicsWorld)
+  fn physics_rotation(&self, rotation: f32) -> f32 {
+    match self {
+      ObjectSet::Walls => rotation,
+      _ => -rotation,
+    }
+  }
+}
+
+enum Edit {
+  Move(f32, f32),
+  Resize(f32, f32),
+  Rotate(f32),
+  Recolor,
+  Add,
+  Delete,
+}
+
+///Pushed on top of the running state (via the pause menu) to edit the current level in place.
+///Every change is written to the level list and the level is restarted so what's shown is always what would be played.
+#[derive(Default)]
+pub struct EditorState {
+  ui: Option<Entity>,
+  grid: Option<Entity>,
+  highlight: Option<Entity>,
+  set: Option<ObjectSet>,
+  index: usize,
+  color_index: usize,
+  snap: bool,
+  //Restart the level once it's finished loading from the last edit
+  dirty: bool,
+  message: String,
+  //Put back when the editor closes if the grid had to be turned on
+  prev_line_width: Option<f32>,
+}
+
+impl<'a, 'b> SimpleState<'a, 'b> for EditorState {
+  fn on_start(&mut self, data: StateData<GameData>) {
+    info!("EditorState.on_start");
+    let world = data.world;
+
+    self.ui = Some(create_ui(world, "ui/editor.ron"));
+    self.set = Some(ObjectSet::Walls);
+    self.snap = true;
+
+    //The grid is always there if it's turned on in the config, otherwise it only shows while editing
+    let gridline_width = world.read_resource::<CameraConfig>().gridline_width;
+    if gridline_width <= 0.0 {
+      {
+        let mut params = world.write_resource::<DebugLinesParams>();
+        self.prev_line_width = Some(params.line_width);
+        params.line_width = 1.0;
+      }
+      self.grid = Some(world
+        .create_entity()
+        .with(grid_lines())
+        .build());
+    }
+
+    world.write_resource::<Level>().editing = true;
+
+    self.refresh(world);
+  }
+  fn on_stop(&mut self, data: StateData<GameData>) {
+    let world = data.world;
+    remove_ui(world, self.ui.take());
+    remove_lines(world, self.grid.take());
+    remove_lines(world, self.highlight.take());
+    if let Some(line_width) = self.prev_line_width.take() {
+      world.write_resource::<DebugLinesParams>().line_width = line_width;
+    }
+    world.write_resource::<Level>().editing = false;
+
+    //Play the edited level from the start
+    world
+      .write_resource::<CommandChannel>()
+      .single_write(Command::RestartLevel);
+  }
+  fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans<'a, 'b> {
+    let world = data.world;
+    let event = match &event {
+      StateEvent::Window(event) => event,
+      _ => return Trans::None,
+    };
+
+    if is_key_down(&event, VirtualKeyCode::Escape) {
+      return Trans::Pop;
+    }
+
+    let step = if self.snap { SNAP } else { 1.0 };
+    let edit = if is_key_down(&event, VirtualKeyCode::Left) {
+      Some(Edit::Move(-step, 0.0))
+    } else if is_key_down(&event, VirtualKeyCode::Right) {
+      Some(Edit::Move(step, 0.0))
+    } else if is_key_down(&event, VirtualKeyCode::Up) {
+      Some(Edit::Move(0.0, step))
+    } else if is_key_down(&event, VirtualKeyCode::Down) {
+      Some(Edit::Move(0.0, -step))
+    } else if is_key_down(&event, VirtualKeyCode::J) {
+      Some(Edit::Resize(-step, 0.0))
+    } else if is_key_down(&event, VirtualKeyCode::L) {
+      Some(Edit::Resize(step, 0.0))
+    } else if is_key_down(&event, VirtualKeyCode::I) {
+      Some(Edit::Resize(0.0, step))
+    } else if is_key_down(&event, VirtualKeyCode::K) {
+      Some(Edit::Resize(0.0, -step))
+    } else if is_key_down(&event, VirtualKeyCode::Q) {
+      Some(Edit::Rotate(ROTATION_STEP))
+    } else if is_key_down(&event, VirtualKeyCode::E) {
+      Some(Edit::Rotate(-ROTATION_STEP))
+    } else if is_key_down(&event, VirtualKeyCode::C) {
+      Some(Edit::Recolor)
+    } else if is_key_down(&event, VirtualKeyCode::Insert) {
+      Some(Edit::Add)
+    } else if is_key_down(&event, VirtualKeyCode::Delete) {
+      Some(Edit::Delete)
+    } else {
+      None
+    };
+
+    if let Some(edit) = edit {
+      self.apply(world, edit);
+    } else if is_key_down(&event, VirtualKeyCode::Tab) {
+      self.index += 1;
+    } else if is_key_down(&event, VirtualKeyCode::Key1) {
+      self.select_set(ObjectSet::Walls);
+    } else if is_key_down(&event, VirtualKeyCode::Key2) {
+      self.select_set(ObjectSet::DeadlyAreas);
+    } else if is_key_down(&event, VirtualKeyCode::Key3) {
+      self.select_set(ObjectSet::Exits);
+    } else if is_key_down(&event, VirtualKeyCode::Key4) {
+      self.select_set(ObjectSet::Spawners);
+    } else if is_key_down(&event, VirtualKeyCode::Key5) {
+      self.select_set(ObjectSet::Blocks);
+    } else if is_key_down(&event, VirtualKeyCode::G) {
+      self.snap = !self.snap;
+    } else if is_key_down(&event, VirtualKeyCode::F2) {
+      self.save(world);
+    } else {
+      return Trans::None;
+    }
+
+    self.refresh(world);
+    Trans::None
+  }
+  fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans<'a, 'b> {
+    let world = &mut data.world;
+
+    //Edits are only possible on the hand made levels, generated ones are rebuilt from their seed
+    let (campaign, loaded) = {
+      let level = world.read_resource::<Level>();
+      (level.mode == GameMode::Campaign, level.load_state == LoadState::Loaded)
+    };
+    if !campaign {
+      warn!("Only campaign levels can be edited");
+      return Trans::Pop;
+    }
+
+    if self.dirty && loaded {
+      world
+        .write_resource::<CommandChannel>()
+        .single_write(Command::RestartLevel);
+      self.dirty = false;
+    }
+
+    //Restarting the level moves the camera back to the start so keep pulling it to the selection
+    if let Some(o) = self.selected(world) {
+      for (t, _) in (&mut world.write_storage::<Transform>(), &world.read_storage::<FlyControlTag>()).join() {
+        t.translation.x = o.position.x;
+        t.translation.y = o.position.y;
+      }
+    }
+
+    Trans::None
+  }
+}
+
+impl EditorState {
+  fn select_set(&mut self, set: ObjectSet) {
+    self.set = Some(set);
+    self.index = 0;
+  }
+
+  fn selected(&self, world: &World) -> Option<Cuboid> {
+    let set = self.set?;
+    let level = world.read_resource::<Level>();
+    let cuboids = set.get(&level.levels[level.current_level]).as_ref()?;
+    cuboids.list.get(self.index).cloned()
+  }
+
+  fn apply(&mut self, world: &mut World, edit: Edit) {
+    let set = match self.set {
+      Some(set) => set,
+      None => return,
+    };
+    let snap = self.snap;
+
+    let mut level = world.write_resource::<Level>();
+    let current_level = level.current_level;
+    if !level.unsaved_levels.contains(&current_level) {
+      level.unsaved_levels.push(current_level);
+    }
+    let cuboids = set
+      .get_mut(&mut level.levels[current_level])
+      .get_or_insert_with(|| CuboidSet {
+        list: Vec::new(),
+        color: None,
+      });
+
+    match edit {
+      Edit::Add => {
+        //Copy the selection so the new one starts off the right size, otherwise start with a grid square
+        let mut o = cuboids.list.get(self.index).cloned().unwrap_or(Cuboid {
+          size: Vector3::new(64.0, 64.0, 0.1),
+          position: Vector3::new(0.0, 0.0, 0.0),
+          color: None,
+          rotation: None,
+        });
+        o.position.x += o.size.x;
+        cuboids.list.push(o);
+        self.index = cuboids.list.len() - 1;
+      },
+      Edit::Delete => {
+        if self.index < cuboids.list.len() {
+          cuboids.list.remove(self.index);
+        }
+      },
+      _ => {
+        let o = match cuboids.list.get_mut(self.index) {
+          Some(o) => o,
+          None => return,
+        };
+
+        match edit {
+          Edit::Move(x, y) => {
+            o.position.x = snap_to(o.position.x + x, snap);
+            o.position.y = snap_to(o.position.y + y, snap);
+          },
+          Edit::Resize(x, y) => {
+            o.size.x = snap_to(o.size.x + x, snap).max(MIN_SIZE);
+            o.size.y = snap_to(o.size.y + y, snap).max(MIN_SIZE);
+          },
+          Edit::Rotate(amount) => {
+            let rotation = o.rotation.unwrap_or(0.0) + amount;
+            o.rotation = if rotation.abs() < 0.001 { None } else { Some(rotation) };
+          },
+          Edit::Recolor => {
+            self.color_index = (self.color_index + 1) % PALETTE.len();
+            o.color = PALETTE[self.color_index].map(|c| Color::new(c[0], c[1], c[2], c[3]));
+          },
+          Edit::Add | Edit::Delete => unreachable!(),
+        }
+      },
+    }
+
+    self.dirty = true;
+    self.message.clear();
+  }
+
+  //Writes the level back over its entry in resources/config.ron
+  fn save(&mut self, world: &mut World) {
+    let (current_level, ron) = {
+      let level = world.read_resource::<Level>();
+      (level.current_level, level_to_ron(&level.levels[level.current_level]))
+    };
+
+    let path = game_config_path();
+    let result = ron
+      .map_err(|e| e.to_string())
+      .and_then(|ron| fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|config| replace_level_ron(&config, current_level, &ron)
+          .ok_or_else(|| format!("level {} isn't in the levels list", current_level)))
+        .and_then(|config| fs::write(&path, config).map_err(|e| e.to_string())));
+
+    self.message = match result {
+      Ok(_) => {
+        info!("Saved level {} to {}", current_level, path);
+        world.write_resource::<Level>().unsaved_levels.retain(|&i| i != current_level);
+        format!("SAVED TO {}", path)
+      },
+      Err(e) => {
+        error!("Failed to save level to {}: {}", path, e);
+        format!("SAVE FAILED: {}", e)
+      },
+    };
+  }
+
+  //Updates the selection outline and text
+  fn refresh(&mut self, world: &mut World) {
+    let count = {
+      let level = world.read_resource::<Level>();
+      self.set
+        .and_then(|set| set.get(&level.levels[level.current_level]).as_ref().map(|s| s.list.len()))
+        .unwrap_or(0)
+    };
+    if self.index >= count {
+      self.index = 0;
+    }
+
+    remove_lines(world, self.highlight.take());
+    let selected = self.selected(world);
+    let set = self.set.unwrap_or(ObjectSet::Walls);
+
+    let text = match selected {
+      Some(ref o) => {
+        let mut lines = DebugLinesComponent::new().with_capacity(4);
+        let corners = corners(o, set.physics_rotation(o.rotation.unwrap_or(0.0)));
+        for i in 0..4 {
+          let (a, b) = (corners[i], corners[(i + 1) % 4]);
+          lines.add_line(
+            [a.0, a.1, 0.0].into(),
+            [b.0, b.1, 0.0].into(),
+            HIGHLIGHT_COLOR.into(),
+          );
+        }
+        self.highlight = Some(world
+          .create_entity()
+          .with(lines)
+          .build());
+
+        format!("{} {}/{}  POS {:.*},{:.*}  SIZE {:.*}x{:.*}  ROT {:.*}",
+          set.name(), self.index + 1, count,
+          0, o.position.x, 0, o.position.y,
+          0, o.size.x, 0, o.size.y,
+          0, o.rotation.unwrap_or(0.0).to_degrees())
+      },
+      None => format!("{} (NONE)", set.name()),
+    };
+
+    set_text(world, "editor_selection", &text);
+    set_text(world, "editor_snap", if self.snap { "SNAP: ON" } else { "SNAP: OFF" });
+    set_text(world, "editor_message", &self.message);
+  }
+}
+
+fn remove_lines(world: &mut World, lines: Option<Entity>) {
+  if let Some(lines) = lines {
+    world
+      .delete_entity(lines)
+      .expect("Failed to remove debug lines");
+  }
+}
+
+fn snap_to(value: f32, snap: bool) -> f32 {
+  if snap {
+    (value / SNAP).round() * SNAP
+  } else {
+    value
+  }
+}
+
+fn corners(o: &Cuboid, rotation: f32) -> [(f32, f32); 4] {
+  let (sin, cos) = rotation.sin_cos();
+  let (hw, hh) = (o.size.x * 0.5, o.size.y * 0.5);
+  let rotate = |x: f32, y: f32| (o.position.x + x * cos - y * sin, o.position.y + x * sin + y * cos);
+  [rotate(-hw, -hh), rotate(hw, -hh), rotate(hw, hh), rotate(-hw, hh)]
+}
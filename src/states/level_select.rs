@@ -0,0 +1,151 @@
+use amethyst::{
+  assets::Handle,
+  prelude::*,
+  ecs::prelude::*,
+  input::is_key_down,
+  ui::UiPrefab,
+  winit::VirtualKeyCode,
+};
+
+use ::resources::{
+  Command,
+  CommandChannel,
+  Level,
+  SaveData,
+};
+
+use super::{
+  PausedState,
+  RunningState,
+  menu::{
+    create_ui,
+    remove_ui,
+    clicked_id,
+    set_text,
+  },
+};
+
+///Where the level select was opened from, decides where it goes back to
+enum Origin {
+  //Sitting on top of the main menu, needs the ui handle to start the running state
+  MainMenu(Handle<UiPrefab>),
+  //Switched in from the pause menu, running state is underneath
+  Paused,
+}
+
+///Lets the player pick which level to play. Shows one level at a time and cycles through them.
+pub struct LevelSelectState {
+  origin: Origin,
+  ui: Option<Entity>,
+  selected: usize,
+}
+
+impl<'a, 'b> SimpleState<'a, 'b> for LevelSelectState {
+  fn on_start(&mut self, data: StateData<GameData>) {
+    info!("LevelSelectState.on_start");
+    self.selected = data.world.read_resource::<Level>().current_level;
+    self.ui = Some(create_ui(data.world, "ui/level_select.ron"));
+  }
+  fn on_stop(&mut self, data: StateData<GameData>) {
+    remove_ui(data.world, self.ui.take());
+  }
+  fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans<'a, 'b> {
+    let level_count = data.world.read_resource::<Level>().levels.len();
+
+    let mut action = clicked_id(data.world, &event);
+    if let StateEvent::Window(event) = &event {
+      if is_key_down(&event, VirtualKeyCode::Escape) {
+        action = Some("back".to_string());
+      } else if is_key_down(&event, VirtualKeyCode::Left) {
+        action = Some("prev".to_string());
+      } else if is_key_down(&event, VirtualKeyCode::Right) {
+        action = Some("next".to_string());
+      } else if is_key_down(&event, VirtualKeyCode::Return) {
+        action = Some("play".to_string());
+      }
+    }
+
+    match action.as_ref().map(|id| id.as_str()) {
+      Some("prev") => {
+        if self.selected > 0 {
+          self.selected -= 1;
+        }
+        Trans::None
+      },
+      Some("next") => {
+        if self.selected + 1 < level_count {
+          self.selected += 1;
+        }
+        Trans::None
+      },
+      Some("play") => {
+        if !data.world.read_resource::<SaveData>().is_unlocked(self.selected) {
+          return Trans::None;
+        }
+        data.world
+          .write_resource::<CommandChannel>()
+          .single_write(Command::LoadLevel(self.selected));
+        match &self.origin {
+          Origin::MainMenu(running_ui_handle) => Trans::Switch(Box::new(RunningState::new(running_ui_handle.clone()))),
+          Origin::Paused => Trans::Pop,
+        }
+      },
+      Some("back") => match &self.origin {
+        Origin::MainMenu(_) => Trans::Pop,
+        Origin::Paused => Trans::Switch(Box::new(PausedState::default())),
+      },
+      _ => Trans::None,
+    }
+  }
+  fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans<'a, 'b> {
+    self.update_ui(data.world);
+    Trans::None
+  }
+}
+
+impl LevelSelectState {
+  pub fn from_menu(running_ui_handle: Handle<UiPrefab>) -> Self {
+    Self::new(Origin::MainMenu(running_ui_handle))
+  }
+
+  pub fn from_pause() -> Self {
+    Self::new(Origin::Paused)
+  }
+
+  fn new(origin: Origin) -> Self {
+    Self {
+      origin,
+      ui: None,
+      selected: 0,
+    }
+  }
+
+  fn update_ui(&self, world: &mut World) {
+    let (number, name, description, status, best) = {
+      let level = world.read_resource::<Level>();
+      let save_data = world.read_resource::<SaveData>();
+      match level.levels.get(self.selected) {
+        Some(config) => (
+          format!("LEVEL {}/{}", self.selected + 1, level.levels.len()),
+          config.name.clone().unwrap_or_default(),
+          config.description.clone().unwrap_or_default(),
+          if save_data.is_unlocked(self.selected) { "UNLOCKED" } else { "LOCKED" },
+          match save_data.best(self.selected) {
+            Some(best) => format!("BEST: {:.*}% SAVED IN {:.*}s, {}/{} STARS",
+              0, best.stats.saved_ratio() * 100.0,
+              1, best.clear_time,
+              best.stars, config.scoring().max_stars()),
+            None => "NOT COMPLETED".to_string(),
+          },
+        ),
+        None => return,
+      }
+    };
+
+    set_text(world, "level_number", &number);
+    set_text(world, "level_name", &name);
+    set_text(world, "level_description", &description);
+    set_text(world, "level_status", status);
+    set_text(world, "level_best", &best);
+  }
+}
@@ -27,6 +27,7 @@ use ::{
     CameraConfig,
   },
   resources::{
+    AudioSettings,
     PhysicsWorld,
     Sounds,
     Sprites,
@@ -34,11 +35,11 @@ use ::{
 };
 
 use super::{
-  RunningState,
+  MainMenuState,
   RunningPrefabData,
 };
 
-///Loads required assets and makes sure everythin is ready before moving to the running state.
+///Loads required assets and makes sure everythin is ready before moving to the main menu.
 #[derive(Default)]
 pub struct LoadingState {
   progress: ProgressCounter,
@@ -83,7 +84,7 @@ impl<'a, 'b> SimpleState<'a, 'b> for LoadingState {
 
       return Trans::Switch(
         Box::new(
-          RunningState::new(running_ui, running_prefab)
+          MainMenuState::new(running_ui, running_prefab)
         )
       )
     }
@@ -128,12 +129,17 @@ impl LoadingState {
   }
 
   fn load_sounds(&mut self, world: &mut World) {
-    let sounds = Sounds::new(
+    let mut sounds = Sounds::new(
       &world.read_resource(), //Loader
       &world.read_resource(), //AssetStorage<Source>
       &mut self.progress,
       &world.read_resource(), //SoundConfig
+      &world.read_resource(), //LevelsConfig
     );
+    //The player's own settings go over the defaults from the config
+    world
+      .read_resource::<AudioSettings>()
+      .apply(&world.read_resource(), &mut sounds);
     world.add_resource(sounds);
   }
 
@@ -171,6 +177,15 @@ fn add_debug_lines(world: &mut World) {
     let mut params = world.write_resource::<DebugLinesParams>();
     params.line_width = gridline_width;
   }
+
+  world
+    .create_entity()
+    .with(grid_lines())
+    .build();
+}
+
+///Gridlines every 64 pixels (1 physics meter)
+pub fn grid_lines() -> DebugLinesComponent {
   let mut debug_lines_component = DebugLinesComponent::new().with_capacity(600);
   for n in 0..100 {
     let nf = n as f32;
@@ -211,8 +226,5 @@ fn add_debug_lines(world: &mut World) {
     );
   }
 
-  world
-    .create_entity()
-    .with(debug_lines_component)
-    .build();
+  debug_lines_component
 }
\ No newline at end of file
@@ -0,0 +1,134 @@
+use amethyst::{
+  assets::{
+    Prefab,
+    Handle,
+  },
+  prelude::*,
+  ecs::prelude::*,
+  input::is_key_down,
+  ui::UiPrefab,
+  winit::VirtualKeyCode,
+};
+
+use chrono::Local;
+
+use ::resources::{
+  Command,
+  CommandChannel,
+  Level,
+  SaveData,
+  DailyHistory,
+};
+
+use super::{
+  RunningState,
+  RunningPrefabData,
+  LevelSelectState,
+  menu::{
+    create_ui,
+    remove_ui,
+    clicked_id,
+    set_text,
+  },
+};
+
+///First thing the player sees once loading is done. Sits at the bottom of the state stack.
+pub struct MainMenuState {
+  running_ui_handle: Handle<UiPrefab>,
+  running_prefab_handle: Handle<Prefab<RunningPrefabData>>,
+  ui: Option<Entity>,
+}
+
+impl<'a, 'b> SimpleState<'a, 'b> for MainMenuState {
+  fn on_start(&mut self, data: StateData<GameData>) {
+    info!("MainMenuState.on_start");
+    let world = data.world;
+
+    //The scene (camera, lights) lives for the whole game so it's created here rather than in RunningState
+    world
+      .create_entity()
+      .with(self.running_prefab_handle.clone())
+      .build();
+
+    self.ui = Some(create_ui(world, "ui/main_menu.ron"));
+  }
+  fn on_stop(&mut self, data: StateData<GameData>) {
+    remove_ui(data.world, self.ui.take());
+  }
+  fn on_pause(&mut self, data: StateData<GameData>) {
+    remove_ui(data.world, self.ui.take());
+  }
+  fn on_resume(&mut self, data: StateData<GameData>) {
+    self.ui = Some(create_ui(data.world, "ui/main_menu.ron"));
+  }
+  fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans<'a, 'b> {
+    if let StateEvent::Window(event) = &event {
+      if is_key_down(&event, VirtualKeyCode::Escape) {
+        return Trans::Quit;
+      }
+    }
+
+    match clicked_id(data.world, &event).as_ref().map(|id| id.as_str()) {
+      Some("play") => {
+        //Carry on from the furthest level the player has reached
+        let level = {
+          let level = data.world.read_resource::<Level>();
+          let save_data = data.world.read_resource::<SaveData>();
+          save_data.unlocked.min(level.levels.len().saturating_sub(1))
+        };
+        data.world
+          .write_resource::<CommandChannel>()
+          .single_write(Command::LoadLevel(level));
+        Trans::Push(Box::new(RunningState::new(self.running_ui_handle.clone())))
+      },
+      Some("endless") => {
+        //New terrain every run
+        let seed = ::rand::random();
+        data.world
+          .write_resource::<CommandChannel>()
+          .single_write(Command::StartEndless(seed));
+        Trans::Push(Box::new(RunningState::new(self.running_ui_handle.clone())))
+      },
+      Some("time_attack") => {
+        data.world
+          .write_resource::<CommandChannel>()
+          .single_write(Command::StartTimeAttack);
+        Trans::Push(Box::new(RunningState::new(self.running_ui_handle.clone())))
+      },
+      Some("daily") => {
+        data.world
+          .write_resource::<CommandChannel>()
+          .single_write(Command::StartDaily(Local::today().naive_local()));
+        Trans::Push(Box::new(RunningState::new(self.running_ui_handle.clone())))
+      },
+      Some("level_select") => Trans::Push(Box::new(LevelSelectState::from_menu(self.running_ui_handle.clone()))),
+      Some("quit") => Trans::Quit,
+      _ => Trans::None,
+    }
+  }
+  fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans<'a, 'b> {
+    let info = {
+      let today = Local::today().naive_local();
+      let history = data.world.read_resource::<DailyHistory>();
+      let today_text = match history.get(today) {
+        Some(record) if record.completed => format!("TODAY'S BEST: {}", record.best_score),
+        Some(_) => "TODAY: NOT COMPLETED".to_string(),
+        None => "TODAY: NOT PLAYED".to_string(),
+      };
+      format!("DAILY STREAK: {} (BEST {})  {}", history.streak(today), history.best_streak(), today_text)
+    };
+    set_text(data.world, "daily_info", &info);
+
+    Trans::None
+  }
+}
+
+impl MainMenuState {
+  pub fn new(running_ui_handle: Handle<UiPrefab>, running_prefab_handle: Handle<Prefab<RunningPrefabData>>) -> Self {
+    Self {
+      running_ui_handle,
+      running_prefab_handle,
+      ui: None,
+    }
+  }
+}
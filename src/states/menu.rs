@@ -0,0 +1,55 @@
+use amethyst::{
+  prelude::*,
+  ecs::prelude::*,
+  ui::{
+    UiCreator,
+    UiEventType,
+    UiFinder,
+    UiText,
+    UiTransform,
+  },
+};
+
+//Small helpers shared by the menu states so they don't all have to track button entities by hand
+
+///Creates the ui described by the prefab at path (relative to assets) and returns the root entity
+pub fn create_ui(world: &mut World, path: &str) -> Entity {
+  world.exec(|mut creator: UiCreator| creator.create(path, ()))
+}
+
+///Deletes a ui previously created with create_ui
+pub fn remove_ui(world: &mut World, root: Option<Entity>) {
+  if let Some(root) = root {
+    world
+      .delete_entity(root)
+      .expect("Failed to remove ui");
+  }
+}
+
+///If the event is a click on a ui element, returns the id of the element that was clicked
+pub fn clicked_id(world: &World, event: &StateEvent) -> Option<String> {
+  match event {
+    StateEvent::Ui(ui_event) => {
+      if let UiEventType::Click = ui_event.event_type {
+        world
+          .read_storage::<UiTransform>()
+          .get(ui_event.target)
+          .map(|t| t.id.clone())
+      } else {
+        None
+      }
+    },
+    _ => None,
+  }
+}
+
+///Replaces the text of the ui element with the given id. Does nothing if it hasn't loaded yet
+pub fn set_text(world: &mut World, id: &str, text: &str) {
+  if let Some(entity) = world.exec(|finder: UiFinder| finder.find(id)) {
+    if let Some(ui_text) = world.write_storage::<UiText>().get_mut(entity) {
+      if ui_text.text != text {
+        ui_text.text = text.to_string();
+      }
+    }
+  }
+}
@@ -1,8 +1,21 @@
 mod loading;
 mod running;
+mod paused;
+mod level_select;
+mod settings;
+mod menu;
+mod main_menu;
+mod result;
+mod editor;
 
 pub use self::loading::LoadingState;
 pub use self::running::{
   RunningState,
   RunningPrefabData,
-};
\ No newline at end of file
+};
+pub use self::paused::PausedState;
+pub use self::level_select::LevelSelectState;
+pub use self::settings::SettingsState;
+pub use self::main_menu::MainMenuState;
+pub use self::result::ResultState;
+pub use self::editor::EditorState;
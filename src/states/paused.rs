@@ -0,0 +1,76 @@
+use amethyst::{
+  prelude::*,
+  ecs::prelude::*,
+  input::is_key_down,
+  winit::VirtualKeyCode,
+};
+
+use ::resources::{
+  Command,
+  CommandChannel,
+  GameMode,
+  Level,
+};
+
+use super::{
+  LevelSelectState,
+  SettingsState,
+  EditorState,
+  menu::{
+    create_ui,
+    remove_ui,
+    clicked_id,
+  },
+};
+
+///Pushed on top of the running state. The running state freezes the simulation while it's paused so all
+///this has to do is show the menu and react to it.
+#[derive(Default)]
+pub struct PausedState {
+  ui: Option<Entity>,
+  //Only campaign levels can be edited so the button is only there for them
+  editor_ui: Option<Entity>,
+}
+
+impl<'a, 'b> SimpleState<'a, 'b> for PausedState {
+  fn on_start(&mut self, data: StateData<GameData>) {
+    info!("PausedState.on_start");
+    self.ui = Some(create_ui(data.world, "ui/paused.ron"));
+    if data.world.read_resource::<Level>().mode == GameMode::Campaign {
+      self.editor_ui = Some(create_ui(data.world, "ui/paused_editor.ron"));
+    }
+  }
+  fn on_stop(&mut self, data: StateData<GameData>) {
+    remove_ui(data.world, self.ui.take());
+    remove_ui(data.world, self.editor_ui.take());
+  }
+  fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans<'a, 'b> {
+    if let StateEvent::Window(event) = &event {
+      if is_key_down(&event, VirtualKeyCode::Escape) {
+        return Trans::Pop;
+      }
+    }
+
+    match clicked_id(data.world, &event).as_ref().map(|id| id.as_str()) {
+      Some("resume") => Trans::Pop,
+      Some("restart") => {
+        data.world
+          .write_resource::<CommandChannel>()
+          .single_write(Command::RestartLevel);
+        Trans::Pop
+      },
+      Some("level_select") => Trans::Switch(Box::new(LevelSelectState::from_pause())),
+      Some("settings") => Trans::Switch(Box::new(SettingsState::default())),
+      Some("editor") => Trans::Switch(Box::new(EditorState::default())),
+      //The running state goes back to the main menu once the level has been unloaded
+      Some("main_menu") => {
+        data.world
+          .write_resource::<CommandChannel>()
+          .single_write(Command::UnloadLevel);
+        Trans::Pop
+      },
+      Some("quit") => Trans::Quit,
+      _ => Trans::None,
+    }
+  }
+}
@@ -0,0 +1,224 @@
+use amethyst::{
+  prelude::*,
+  ecs::prelude::*,
+  input::is_key_down,
+  winit::VirtualKeyCode,
+};
+
+use chrono::NaiveDate;
+
+use ::resources::{
+  Command,
+  CommandChannel,
+  Level,
+  Outcome,
+  SpawnStats,
+  GameMode,
+  EndlessRun,
+  SaveData,
+  DailyHistory,
+  daily_score,
+  TimeAttack,
+};
+
+use super::{
+  LevelSelectState,
+  menu::{
+    create_ui,
+    remove_ui,
+    clicked_id,
+    set_text,
+  },
+};
+
+///Overlay pushed on top of the running state when a level is won or lost.
+pub struct ResultState {
+  outcome: Outcome,
+  ui: Option<Entity>,
+}
+
+impl<'a, 'b> SimpleState<'a, 'b> for ResultState {
+  fn on_start(&mut self, data: StateData<GameData>) {
+    info!("ResultState.on_start");
+    self.ui = Some(create_ui(data.world, "ui/result.ron"));
+  }
+  fn on_stop(&mut self, data: StateData<GameData>) {
+    remove_ui(data.world, self.ui.take());
+  }
+  fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans<'a, 'b> {
+    let mut action = clicked_id(data.world, &event);
+    if let StateEvent::Window(event) = &event {
+      if is_key_down(&event, VirtualKeyCode::R) {
+        action = Some("retry".to_string());
+      } else if is_key_down(&event, VirtualKeyCode::N) || is_key_down(&event, VirtualKeyCode::Return) {
+        action = Some("next".to_string());
+      } else if is_key_down(&event, VirtualKeyCode::M) {
+        action = Some("main_menu".to_string());
+      }
+    }
+
+    match action.as_ref().map(|id| id.as_str()) {
+      Some("retry") => {
+        data.world
+          .write_resource::<CommandChannel>()
+          .single_write(Command::RestartLevel);
+        Trans::Pop
+      },
+      //The running state goes back to the main menu once the level has been unloaded
+      Some("main_menu") => {
+        data.world
+          .write_resource::<CommandChannel>()
+          .single_write(Command::UnloadLevel);
+        Trans::Pop
+      },
+      //Generated levels don't have a next level
+      Some("next") if self.outcome == Outcome::Won && data.world.read_resource::<Level>().mode == GameMode::Campaign => {
+        let last_level = {
+          let level = data.world.read_resource::<Level>();
+          level.current_level + 1 >= level.levels.len()
+        };
+        if last_level {
+          //Nowhere to go next, let them pick something to replay
+          Trans::Switch(Box::new(LevelSelectState::from_pause()))
+        } else {
+          data.world
+            .write_resource::<CommandChannel>()
+            .single_write(Command::NextLevel);
+          Trans::Pop
+        }
+      },
+      _ => Trans::None,
+    }
+  }
+  fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans<'a, 'b> {
+    let world = &mut data.world;
+
+    let mode = world.read_resource::<Level>().mode;
+    if let GameMode::Endless(_) = mode {
+      self.update_endless(world);
+      return Trans::None;
+    }
+
+    let (saved, killed, time, stars, par) = {
+      let spawn_stats = world.read_resource::<SpawnStats>();
+      let level = world.read_resource::<Level>();
+      let scoring = level.config().scoring();
+
+      let stars = match self.outcome {
+        Outcome::Won => format!("STARS: {}/{}", scoring.stars(&spawn_stats, level.runtime), scoring.max_stars()),
+        Outcome::Lost => String::new(),
+      };
+
+      let mut par = Vec::new();
+      if let Some(par_time) = scoring.par_time {
+        par.push(format!("PAR TIME: {:.*}s", 0, par_time));
+      }
+      if let Some(par_abilities) = scoring.par_abilities {
+        par.push(format!("PAR ABILITIES: {} (USED {})", par_abilities, spawn_stats.abilities_used));
+      }
+
+      (
+        format!("SAVED: {}/{} ({:.*}%)", spawn_stats.saved, spawn_stats.total, 0, spawn_stats.saved_ratio() * 100.0),
+        format!("KILLED: {}", spawn_stats.killed),
+        format!("TIME: {:.*}s", 1, level.runtime),
+        stars,
+        par.join("  "),
+      )
+    };
+
+    set_text(world, "result_title", match self.outcome {
+      Outcome::Won => "LEVEL COMPLETE",
+      Outcome::Lost => "LEVEL FAILED",
+    });
+    set_text(world, "result_saved", &saved);
+    set_text(world, "result_killed", &killed);
+    set_text(world, "result_time", &time);
+    set_text(world, "result_stars", &stars);
+    set_text(world, "result_par", &par);
+    if let GameMode::Daily(date) = mode {
+      self.update_daily(world, date);
+    } else if self.outcome == Outcome::Won && world.read_resource::<TimeAttack>().active {
+      self.update_time_attack(world);
+    }
+    set_text(world, "result_hint", match self.outcome {
+      Outcome::Won => "",
+      Outcome::Lost => "NOT ENOUGH CREEPS CAN BE SAVED",
+    });
+
+    Trans::None
+  }
+}
+
+impl ResultState {
+  pub fn new(outcome: Outcome) -> Self {
+    Self {
+      outcome,
+      ui: None,
+    }
+  }
+
+  //Replaces the par line with the daily score and streak
+  fn update_daily(&self, world: &mut World, date: NaiveDate) {
+    let daily = {
+      let spawn_stats = world.read_resource::<SpawnStats>();
+      let level = world.read_resource::<Level>();
+      let history = world.read_resource::<DailyHistory>();
+      let best = history.get(date).map_or(0, |r| r.best_score);
+      let streak = history.streak(date);
+      match self.outcome {
+        Outcome::Won => format!("SCORE: {}  BEST: {}  STREAK: {}", daily_score(spawn_stats.saved, level.runtime), best, streak),
+        Outcome::Lost => format!("BEST: {}  STREAK: {}", best, streak),
+      }
+    };
+
+    set_text(world, "result_par", &daily);
+  }
+
+  //Replaces the par line with the time attack splits
+  fn update_time_attack(&self, world: &mut World) {
+    let splits = {
+      let time_attack = world.read_resource::<TimeAttack>();
+      let level = world.read_resource::<Level>();
+      let save_data = world.read_resource::<SaveData>();
+      let records = &save_data.time_attack;
+
+      let mut splits = Vec::new();
+      if let Some(best) = records.best_level(level.current_level) {
+        splits.push(format!("LEVEL BEST: {:.*}s", 2, best));
+      }
+      match time_attack.last_delta(records) {
+        Some(delta) => splits.push(format!("SPLIT: {:.*}s ({:+.*})", 2, time_attack.run_time, 2, delta)),
+        None => splits.push(format!("SPLIT: {:.*}s", 2, time_attack.run_time)),
+      }
+      if time_attack.finished {
+        if let Some(best) = records.best_run() {
+          splits.push(format!("BEST RUN: {:.*}s", 2, best));
+        }
+      }
+      splits.join("  ")
+    };
+
+    set_text(world, "result_par", &splits);
+  }
+
+  fn update_endless(&self, world: &mut World) {
+    let (distance, score, time, best) = {
+      let endless_run = world.read_resource::<EndlessRun>();
+      let level = world.read_resource::<Level>();
+      (
+        format!("DISTANCE: {}m", endless_run.distance_meters()),
+        format!("SCORE: {}", endless_run.score(0)),
+        format!("TIME: {:.*}s", 1, level.runtime),
+        format!("BEST SCORE: {}", world.read_resource::<SaveData>().best_endless_score),
+      )
+    };
+
+    set_text(world, "result_title", "RUN OVER");
+    set_text(world, "result_saved", &distance);
+    set_text(world, "result_killed", &score);
+    set_text(world, "result_time", &time);
+    set_text(world, "result_stars", &best);
+    set_text(world, "result_par", "");
+    set_text(world, "result_hint", "EVERY CREEP IS GONE");
+  }
+}
@@ -1,8 +1,5 @@
 use amethyst::{
-  assets::{
-    Prefab,
-    Handle,
-  },
+  assets::Handle,
   core::Time,
   ecs::prelude::*,
   prelude::*,
@@ -27,9 +24,21 @@ use ::{
   resources::{
     SpawnStats,
     Level,
+    LoadState,
+    Simulation,
+    GameMode,
+    EndlessRun,
+    TimeAttack,
+    SaveData,
   },
 };
 
+use super::{
+  PausedState,
+  ResultState,
+  menu::remove_ui,
+};
+
 const UI_UPDATE_FRAMES: u64 = 20; //How many frames to wait between ui updates
 
 pub type RunningPrefabData = BasicScenePrefab<Vec<PosNormTex>>;
@@ -37,7 +46,7 @@ pub type RunningPrefabData = BasicScenePrefab<Vec<PosNormTex>>;
 ///Main state of the game. Main work done here is updating the gui.
 pub struct RunningState {
   running_ui_handle: Handle<UiPrefab>,
-  running_prefab_handle: Handle<Prefab<RunningPrefabData>>,
+  ui: Option<Entity>,
   fps_display: Option<Entity>,
 
   spawned_display: Option<Entity>,
@@ -46,6 +55,15 @@ pub struct RunningState {
 
   name_display: Option<Entity>,
   description_display: Option<Entity>,
+
+  timer_display: Option<Entity>,
+  split_display: Option<Entity>,
+  time_scale_display: Option<Entity>,
+
+  //Stops the result screen being shown again before the next level has loaded
+  result_shown: bool,
+  //Set once the first level is loaded, after that the level being unloaded means going back to the main menu
+  level_loaded: bool,
 }
 
 impl<'a, 'b> SimpleState<'a, 'b> for RunningState {
@@ -53,14 +71,24 @@ impl<'a, 'b> SimpleState<'a, 'b> for RunningState {
     info!("RunningState.on_start");
     let world = data.world;
 
-    self.initialise_prefab(world);
     self.initialise_ui(world);
   }
+  //Only happens when going back to the main menu
+  fn on_stop(&mut self, data: StateData<GameData>) {
+    remove_ui(data.world, self.ui.take());
+  }
+  //Anything pushed on top of this state (menus etc.) freezes the game until it's popped again
+  fn on_pause(&mut self, data: StateData<GameData>) {
+    data.world.write_resource::<Simulation>().paused = true;
+  }
+  fn on_resume(&mut self, data: StateData<GameData>) {
+    data.world.write_resource::<Simulation>().paused = false;
+  }
   fn handle_event(&mut self, _data: StateData<GameData>, event: StateEvent) -> SimpleTrans<'a, 'b> {
     match &event {
       StateEvent::Window(event) => {
         if is_key_down(&event, VirtualKeyCode::Escape) {
-          return Trans::Quit;
+          return Trans::Push(Box::new(PausedState::default()));
         }
       },
       _ => {},
@@ -70,28 +98,58 @@ impl<'a, 'b> SimpleState<'a, 'b> for RunningState {
   fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans<'a, 'b> {
     let world = &mut data.world;
 
+    //The ui loads asynchronously so keep looking for anything that hasn't been found yet
+    self.find_ui_components(world);
+
     let frame_number = world.read_resource::<Time>().frame_number();
-    if frame_number < UI_UPDATE_FRAMES {
-      self.find_ui_components(world);
-    } else if frame_number % UI_UPDATE_FRAMES == 0 {
+    if frame_number % UI_UPDATE_FRAMES == 0 {
       self.update_ui(world);
     }
+    //Updated every frame, a timer that jumps looks broken
+    self.update_timer(world);
+
+    //Only UnloadLevel leaves a level unloaded, restarting or changing level goes straight on to the next load
+    let unloaded = {
+      let level = world.read_resource::<Level>();
+      if level.load_state == LoadState::Loaded {
+        self.level_loaded = true;
+      }
+      level.load_state == LoadState::Unloaded
+    };
+    if self.level_loaded && unloaded {
+      return Trans::Pop;
+    }
+
+    let outcome = world.read_resource::<Level>().outcome;
+    match outcome {
+      Some(outcome) if !self.result_shown => {
+        self.result_shown = true;
+        return Trans::Push(Box::new(ResultState::new(outcome)));
+      },
+      None => self.result_shown = false,
+      _ => {},
+    }
 
     Trans::None
   }
 }
 
 impl RunningState {
-  pub fn new(running_ui_handle: Handle<UiPrefab>, running_prefab_handle: Handle<Prefab<RunningPrefabData>>) -> Self {
+  pub fn new(running_ui_handle: Handle<UiPrefab>) -> Self {
     Self {
       running_ui_handle,
-      running_prefab_handle,
+      ui: None,
       fps_display: None,
       spawned_display: None,
       needed_percent_display: None,
       saved_percent_display: None,
       name_display: None,
       description_display: None,
+      timer_display: None,
+      split_display: None,
+      time_scale_display: None,
+      result_shown: false,
+      level_loaded: false,
     }
   }
 
@@ -113,7 +171,7 @@ impl RunningState {
 
         if let Some(name_display) = ui_text.get_mut(name_display) {
           if name_display.text.is_empty() {
-            if let Some(ref name) = level.levels[level.current_level].name {
+            if let Some(ref name) = level.config().name {
               name_display.text.push_str(name);
             }
           }
@@ -121,7 +179,7 @@ impl RunningState {
 
         if let Some(description_display) = ui_text.get_mut(description_display) {
           if description_display.text.is_empty() {
-            if let Some(ref description) = level.levels[level.current_level].description {
+            if let Some(ref description) = level.config().description {
               description_display.text.push_str(description);
             }
           }
@@ -170,6 +228,20 @@ impl RunningState {
         spawned_display.text = format!("SPAWNED: {}/{}", spawn_stats.spawned, spawn_stats.total);
       }
 
+      //Endless has nothing to reach so show how the run is going instead
+      if let GameMode::Endless(_) = world.read_resource::<Level>().mode {
+        let endless_run = world.read_resource::<EndlessRun>();
+
+        if let Some(needed_percent_display) = ui_text.get_mut(needed_percent_display) {
+          needed_percent_display.text = format!("DISTANCE: {}m", endless_run.distance_meters());
+        }
+
+        if let Some(saved_percent_display) = ui_text.get_mut(saved_percent_display) {
+          saved_percent_display.text = format!("SCORE: {}", endless_run.score(spawn_stats.alive()));
+        }
+        return;
+      }
+
       if let Some(needed_percent_display) = ui_text.get_mut(needed_percent_display) {
         needed_percent_display.text = format!("% NEEDED: {:.*}", 0, spawn_stats.win_ratio * 100.0);
       }
@@ -180,10 +252,59 @@ impl RunningState {
     }
   }
 
+  fn update_timer(&mut self, world: &mut World) {
+    if let (Some(timer_display), Some(split_display)) = (self.timer_display, self.split_display) {
+      let (timer, split) = {
+        let time_attack = world.read_resource::<TimeAttack>();
+        if time_attack.active {
+          let level = world.read_resource::<Level>();
+          let save_data = world.read_resource::<SaveData>();
+          let records = &save_data.time_attack;
+
+          let timer = match records.best_level(level.current_level) {
+            Some(best) => format!("TIME: {:.*} ({:+.*})", 2, level.runtime, 2, level.runtime - best),
+            None => format!("TIME: {:.*}", 2, level.runtime),
+          };
+          let split = match time_attack.live_delta(level.current_level, records) {
+            Some(delta) => format!("RUN: {:.*} ({:+.*})", 2, time_attack.run_time, 2, delta),
+            None => format!("RUN: {:.*}", 2, time_attack.run_time),
+          };
+          (timer, split)
+        } else {
+          (String::new(), String::new())
+        }
+      };
+
+      let mut ui_text = world.write_storage::<UiText>();
+      if let Some(timer_display) = ui_text.get_mut(timer_display) {
+        timer_display.text = timer;
+      }
+      if let Some(split_display) = ui_text.get_mut(split_display) {
+        split_display.text = split;
+      }
+    }
+  }
+
+  fn update_time_scale(&mut self, world: &mut World) {
+    if let Some(time_scale_display) = self.time_scale_display {
+      let time_scale = world.read_resource::<Simulation>().time_scale;
+      let text = if time_scale == 1.0 {
+        String::new()
+      } else {
+        format!("SPEED: {}x", time_scale)
+      };
+
+      if let Some(time_scale_display) = world.write_storage::<UiText>().get_mut(time_scale_display) {
+        time_scale_display.text = text;
+      }
+    }
+  }
+
   fn update_ui(&mut self, world: &mut World) {
     self.update_name_and_description(world);
     self.update_fps(world);
     self.update_spawn_stats(world);
+    self.update_time_scale(world);
   }
 
   fn find_ui_components(&mut self, world: &mut World) {
@@ -220,6 +341,30 @@ impl RunningState {
       });
     }
 
+    if self.timer_display.is_none() {
+      world.exec(|finder: UiFinder| {
+        if let Some(entity) = finder.find("timer") {
+          self.timer_display = Some(entity);
+        }
+      });
+    }
+
+    if self.split_display.is_none() {
+      world.exec(|finder: UiFinder| {
+        if let Some(entity) = finder.find("split") {
+          self.split_display = Some(entity);
+        }
+      });
+    }
+
+    if self.time_scale_display.is_none() {
+      world.exec(|finder: UiFinder| {
+        if let Some(entity) = finder.find("time_scale") {
+          self.time_scale_display = Some(entity);
+        }
+      });
+    }
+
     if self.name_display.is_none() {
       world.exec(|finder: UiFinder| {
         if let Some(entity) = finder.find("name") {
@@ -237,17 +382,10 @@ impl RunningState {
     }
   }
 
-  fn initialise_prefab(&self, world: &mut World) {
-    world
-      .create_entity()
-      .with(self.running_prefab_handle.clone())
-      .build();
-  }
-
-  fn initialise_ui(&self, world: &mut World) {
-    world
+  fn initialise_ui(&mut self, world: &mut World) {
+    self.ui = Some(world
       .create_entity()
       .with(self.running_ui_handle.clone())
-      .build();
+      .build());
   }
 }
\ No newline at end of file
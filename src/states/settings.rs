@@ -0,0 +1,153 @@
+use amethyst::{
+  prelude::*,
+  ecs::prelude::*,
+  input::is_key_down,
+  ui::{
+    UiFinder,
+    UiTransform,
+  },
+  winit::VirtualKeyCode,
+};
+
+use ::{
+  config::SoundConfig,
+  resources::{
+    AudioSettings,
+    Sounds,
+  },
+};
+
+use super::{
+  PausedState,
+  menu::{
+    create_ui,
+    remove_ui,
+    clicked_id,
+    set_text,
+  },
+};
+
+const VOLUME_STEP: f32 = 0.1;
+//Width of the slider tracks in ui/settings.ron
+const SLIDER_WIDTH: f32 = 240.0;
+
+#[derive(Clone, Copy)]
+enum Slider {
+  Master,
+  Music,
+  Sfx,
+}
+
+impl Slider {
+  //Also the id of the slider's percentage text, the buttons and bar are named after it
+  fn id(&self) -> &'static str {
+    match self {
+      Slider::Master => "volume",
+      Slider::Music => "music_volume",
+      Slider::Sfx => "sfx_volume",
+    }
+  }
+
+  fn value(&self, sounds: &Sounds) -> f32 {
+    match self {
+      Slider::Master => sounds.volume,
+      Slider::Music => sounds.music_volume,
+      Slider::Sfx => sounds.sfx_volume,
+    }
+  }
+
+  fn setting<'a>(&self, settings: &'a mut AudioSettings) -> &'a mut Option<f32> {
+    match self {
+      Slider::Master => &mut settings.volume,
+      Slider::Music => &mut settings.music_volume,
+      Slider::Sfx => &mut settings.sfx_volume,
+    }
+  }
+}
+
+const SLIDERS: [Slider; 3] = [Slider::Master, Slider::Music, Slider::Sfx];
+
+///Settings menu, reached from the pause menu. Changes are saved as soon as they're made.
+#[derive(Default)]
+pub struct SettingsState {
+  ui: Option<Entity>,
+}
+
+impl<'a, 'b> SimpleState<'a, 'b> for SettingsState {
+  fn on_start(&mut self, data: StateData<GameData>) {
+    info!("SettingsState.on_start");
+    self.ui = Some(create_ui(data.world, "ui/settings.ron"));
+  }
+  fn on_stop(&mut self, data: StateData<GameData>) {
+    remove_ui(data.world, self.ui.take());
+  }
+  fn handle_event(&mut self, data: StateData<GameData>, event: StateEvent) -> SimpleTrans<'a, 'b> {
+    if let StateEvent::Window(event) = &event {
+      if is_key_down(&event, VirtualKeyCode::Escape) {
+        return Trans::Switch(Box::new(PausedState::default()));
+      }
+    }
+
+    let clicked = match clicked_id(data.world, &event) {
+      Some(id) => id,
+      None => return Trans::None,
+    };
+
+    if clicked == "back" {
+      return Trans::Switch(Box::new(PausedState::default()));
+    }
+    if clicked == "mute" {
+      change_settings(data.world, |settings, _| settings.muted = !settings.muted);
+      return Trans::None;
+    }
+    for slider in SLIDERS.iter() {
+      let step = if clicked == format!("{}_down", slider.id()) {
+        -VOLUME_STEP
+      } else if clicked == format!("{}_up", slider.id()) {
+        VOLUME_STEP
+      } else {
+        continue;
+      };
+      change_settings(data.world, |settings, sounds| {
+        let value = (slider.value(sounds) + step).min(1.0).max(0.0);
+        *slider.setting(settings) = Some(value);
+      });
+    }
+    Trans::None
+  }
+  fn update(&mut self, data: &mut StateData<GameData>) -> SimpleTrans<'a, 'b> {
+    let (values, muted) = {
+      let sounds = data.world.read_resource::<Sounds>();
+      (SLIDERS.iter().map(|s| s.value(&sounds)).collect::<Vec<_>>(), sounds.muted)
+    };
+
+    for (slider, value) in SLIDERS.iter().zip(values) {
+      set_text(data.world, slider.id(), &format!("{:.*}%", 0, value * 100.0));
+      set_slider_fill(data.world, &format!("{}_fill", slider.id()), value);
+    }
+    set_text(data.world, "mute_status", if muted { "ON" } else { "OFF" });
+    Trans::None
+  }
+}
+
+//Changes the player's settings, puts them into effect and saves them
+fn change_settings<F: FnOnce(&mut AudioSettings, &Sounds)>(world: &mut World, change: F) {
+  let mut settings = world.write_resource::<AudioSettings>();
+  let mut sounds = world.write_resource::<Sounds>();
+  change(&mut settings, &sounds);
+  settings.apply(&world.read_resource::<SoundConfig>(), &mut sounds);
+  settings.save();
+}
+
+//Shrinks the bar towards the left end of the track
+fn set_slider_fill(world: &mut World, id: &str, value: f32) {
+  if let Some(entity) = world.exec(|finder: UiFinder| finder.find(id)) {
+    if let Some(transform) = world.write_storage::<UiTransform>().get_mut(entity) {
+      let width = SLIDER_WIDTH * value;
+      if transform.width != width {
+        transform.width = width;
+        transform.local_x = (width - SLIDER_WIDTH) / 2.0;
+      }
+    }
+  }
+}
@@ -8,7 +8,10 @@ use ::{
     Age as AgeComponent,
     Family,
   },
-  resources::SpawnStats,
+  resources::{
+    SpawnStats,
+    Simulation,
+  },
 };
 
 ///Adds this frames delta time to all Age components
@@ -19,13 +22,14 @@ impl<'s> System<'s> for Age {
   type SystemData = (
     Entities<'s>,
     Read<'s, Time>,
+    Read<'s, Simulation>,
     WriteStorage<'s, AgeComponent>,
     ReadStorage<'s, Family>,
     Write<'s, SpawnStats>,
   );
 
-  fn run(&mut self, (entities, time, mut age, family, mut spawn_stats): Self::SystemData) {
-    let delta = time.delta_seconds();
+  fn run(&mut self, (entities, time, simulation, mut age, family, mut spawn_stats): Self::SystemData) {
+    let delta = simulation.delta(&time);
 
     for (e, a) in (&entities, &mut age).join() {
       if entities.is_alive(e) {
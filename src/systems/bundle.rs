@@ -27,6 +27,17 @@ use super::ConstantVelocity;
 use super::DropRam;
 use super::Level;
 use super::Exodus;
+use super::LevelOutcome;
+use super::Endless;
+use super::Ghost;
+use super::Rewind;
+use super::TimeScale;
+use super::ConfigWatcher;
+use super::Music;
+use super::SoundDispatcher;
+use super::SpriteAnimation;
+use super::CreepAnimation;
+use super::CameraProjection;
 
 ///Bundle containing all user systems for the game
 pub struct GameBundle;
@@ -43,7 +54,9 @@ impl<'a, 'b> SystemBundle<'a, 'b> for GameBundle {
       builder.add(PhysicsVisualizer::default(), "physics_visualizer_system", &[]);
       builder.add(ShapeVisualizer::default(), "shape_visualizer_system", &[]);
       builder.add(PlayerInput::default(), "player_input_system", &[]);
+      builder.add(CameraProjection::default(), "camera_projection_system", &["camera_movement_system", "player_input_system"]);
       builder.add(Indicator::default(), "indicator_system", &[]);
+      builder.add(ConfigWatcher::default(), "config_watcher_system", &[]);
 
       builder.add(Age::default(), "age_system", &[]);
 
@@ -62,7 +75,9 @@ impl<'a, 'b> SystemBundle<'a, 'b> for GameBundle {
         "drop_ram_system",
       ]);
       builder.add(Level::default(), "level_system", &["player_input_system"]);
+      builder.add(Music::default(), "music_system", &["level_system"]);
       builder.add(Exodus::default(), "exodus_system", &["player_input_system"]);
+      builder.add(TimeScale::default(), "time_scale_system", &["player_input_system"]);
 
       //These depend on the player input to reduce the chance of the player trying to do something and the matriarch
       //dying a fraction before they do.
@@ -90,6 +105,33 @@ impl<'a, 'b> SystemBundle<'a, 'b> for GameBundle {
       //if they are one tick behind or not
       builder.add(MatriarchPromote::default(), "matriarch_promotion_system", &["murder_system"]);
 
+      builder.add(Endless::default(), "endless_system", &["physics_step_system", "level_system"]);
+      builder.add(Ghost::default(), "ghost_system", &["physics_step_system", "level_system"]);
+      builder.add(Rewind::default(), "rewind_system", &["physics_step_system", "level_system", "ghost_system"]);
+
+      //Runs after everything that can save or kill a creep so the stats are final for the frame
+      builder.add(LevelOutcome::default(), "level_outcome_system", &[
+        "exit_system",
+        "deadly_area_system",
+        "murder_system",
+        "age_system",
+        "level_system",
+        "endless_system",
+        "ghost_system",
+      ]);
+
+      //After everything that sends sounds so a frame's worth can be combined
+      builder.add(SoundDispatcher::default(), "sound_dispatcher_system", &[
+        "spawner_system",
+        "exodus_system",
+        "exit_system",
+        "deadly_area_system",
+        "launch_area_system",
+      ]);
+
+      builder.add(CreepAnimation::default(), "creep_animation_system", &["physics_step_system"]);
+      builder.add(SpriteAnimation::default(), "sprite_animation_system", &["creep_animation_system"]);
+
       builder.add(PhysicsTransformUpdate::default(), "physics_transform_update_system", &["physics_step_system"]);
 
       //NOTE: builder.print_par_seq was very useful in working out why dependencies seemed to be reversed
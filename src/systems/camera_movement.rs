@@ -6,6 +6,7 @@ use amethyst::{
     Time,
   },
   ecs::prelude::*,
+  renderer::ScreenDimensions,
 };
 
 use ::{
@@ -14,15 +15,23 @@ use ::{
     Walker,
     Direction,
   },
-  config::CameraConfig,
+  config::{
+    Bounds,
+    CameraConfig,
+  },
   resources::{
     Command,
     CommandChannel,
+    GameMode,
+    Level,
+    LoadState,
     SpawnStats,
   },
 };
 
 ///Moves the camera. Tracks the matriarch (or the exit after some have exited).
+///The view is kept inside the level's bounds, apart from in endless mode where the level keeps going.
+///final_position wins over the bounds, once the camera is heading there it isn't clamped so a level can pull out to show everything.
 #[derive(Default)]
 pub struct CameraMovement {
   command_reader: Option<ReaderId<Command>>,
@@ -38,6 +47,8 @@ impl<'s> System<'s> for CameraMovement {
     Read<'s, CameraConfig>,
     Read<'s, CommandChannel>,
     Read<'s, SpawnStats>,
+    Read<'s, Level>,
+    ReadExpect<'s, ScreenDimensions>,
   );
 
   fn setup(&mut self, res: &mut Resources) {
@@ -45,7 +56,7 @@ impl<'s> System<'s> for CameraMovement {
     self.command_reader = Some(res.fetch_mut::<CommandChannel>().register_reader());
   }
 
-  fn run(&mut self, (time, mut transforms, fly_tags, matriarchs, walkers, camera_config, commands, spawn_stats): Self::SystemData) {
+  fn run(&mut self, (time, mut transforms, fly_tags, matriarchs, walkers, camera_config, commands, spawn_stats, level, screen_dimensions): Self::SystemData) {
     let delta = time.delta_seconds();
 
     let mut zoom = 0.0;
@@ -80,6 +91,7 @@ impl<'s> System<'s> for CameraMovement {
       }
     };
 
+    let to_final_position = final_target.map_or(false, |(_, lerp_z)| lerp_z);
     if let Some((final_target, lerp_z)) = final_target {
       for (t, _tag) in (&mut transforms, &fly_tags).join() {
         t.translation.x += (final_target.x - t.translation.x) * delta * camera_config.convergence_speed;
@@ -95,5 +107,43 @@ impl<'s> System<'s> for CameraMovement {
         }
       }
     }
+
+    let endless = match level.mode {
+      GameMode::Endless(_) => true,
+      _ => false,
+    };
+    if level.load_state != LoadState::Loaded || endless || to_final_position {
+      return;
+    }
+
+    if let Some(bounds) = level.config().camera_bounds() {
+      let aspect = screen_dimensions.aspect_ratio();
+      for (t, _tag) in (&mut transforms, &fly_tags).join() {
+        clamp_to_bounds(&mut t.translation, &bounds, &camera_config, aspect);
+      }
+    }
+  }
+}
+
+//Moves the camera so everything it can see is inside the bounds. It's not allowed to zoom out further than
+//the bounds allow and if they're still smaller than the view (because of z_min) it's centered on them
+fn clamp_to_bounds(translation: &mut Vector3<f32>, bounds: &Bounds, camera_config: &CameraConfig, aspect: f32) {
+  let (unit_half_width, unit_half_height) = camera_config.visible_half_extents(1.0, aspect);
+  if unit_half_width > 0.0 && unit_half_height > 0.0 {
+    let fit_z = ((bounds.right - bounds.left) * 0.5 / unit_half_width)
+      .min((bounds.top - bounds.bottom) * 0.5 / unit_half_height);
+    translation.z = translation.z.min(fit_z.max(camera_config.z_min));
+  }
+
+  let (half_width, half_height) = camera_config.visible_half_extents(translation.z, aspect);
+  translation.x = clamp_axis(translation.x, bounds.left, bounds.right, half_width);
+  translation.y = clamp_axis(translation.y, bounds.bottom, bounds.top, half_height);
+}
+
+fn clamp_axis(value: f32, min: f32, max: f32, half_view: f32) -> f32 {
+  if max - min <= half_view * 2.0 {
+    (min + max) * 0.5
+  } else {
+    value.max(min + half_view).min(max - half_view)
   }
 }
\ No newline at end of file
@@ -0,0 +1,111 @@
+use amethyst::{
+  controls::FlyControlTag,
+  core::{
+    cgmath::{
+      Deg,
+      Ortho,
+      PerspectiveFov,
+    },
+    transform::components::Transform,
+  },
+  ecs::prelude::*,
+  renderer::{
+    Camera,
+    Projection,
+    ScreenDimensions,
+  },
+  shrev::ReaderId,
+};
+
+use ::{
+  config::{
+    CameraConfig,
+    ProjectionMode,
+  },
+  resources::{
+    Command,
+    CommandChannel,
+  },
+};
+
+const NEAR: f32 = 0.1;
+const FAR: f32 = 2000.0;
+
+///Keeps the camera's projection in step with CameraConfig and the window's aspect ratio.
+///Orthographic zoom comes from the camera's z so it zooms (and follows overrides) the same as perspective.
+///The config only picks the mode to start in, toggling is kept here so reloading the config doesn't undo it.
+#[derive(Default)]
+pub struct CameraProjection {
+  command_reader: Option<ReaderId<Command>>,
+  mode: Option<ProjectionMode>,
+  //What the projection was last built from (mode, aspect, z)
+  current: Option<(ProjectionMode, f32, f32)>,
+}
+
+impl<'s> System<'s> for CameraProjection {
+  type SystemData = (
+    ReadStorage<'s, Transform>,
+    ReadStorage<'s, FlyControlTag>,
+    WriteStorage<'s, Camera>,
+    Read<'s, CameraConfig>,
+    Read<'s, CommandChannel>,
+    ReadExpect<'s, ScreenDimensions>,
+  );
+
+  fn setup(&mut self, res: &mut Resources) {
+    Self::SystemData::setup(res);
+    self.command_reader = Some(res.fetch_mut::<CommandChannel>().register_reader());
+  }
+
+  fn run(&mut self, (transforms, fly_tags, mut cameras, camera_config, commands, screen_dimensions): Self::SystemData) {
+    let mut mode = *self.mode.get_or_insert(camera_config.projection);
+    for command in commands.read(self.command_reader.as_mut().unwrap()) {
+      match command {
+        Command::ToggleProjection => {
+          mode = match mode {
+            ProjectionMode::Perspective => ProjectionMode::Orthographic,
+            ProjectionMode::Orthographic => ProjectionMode::Perspective,
+          };
+          info!("Switched to {:?} camera", mode);
+        },
+        _ => {},
+      }
+    }
+
+    self.mode = Some(mode);
+
+    let aspect = screen_dimensions.aspect_ratio();
+    for (t, camera, _) in (&transforms, &mut cameras, &fly_tags).join() {
+      //Perspective doesn't change with z
+      let z = match mode {
+        ProjectionMode::Perspective => 0.0,
+        ProjectionMode::Orthographic => t.translation.z,
+      };
+      if self.current == Some((mode, aspect, z)) {
+        continue;
+      }
+      self.current = Some((mode, aspect, z));
+
+      let projection = match mode {
+        ProjectionMode::Perspective => Projection::Perspective(PerspectiveFov {
+          fovy: Deg(camera_config.fov).into(),
+          aspect,
+          near: NEAR,
+          far: FAR,
+        }),
+        ProjectionMode::Orthographic => {
+          let (half_width, half_height) = camera_config.visible_half_extents(z, aspect);
+          Projection::Orthographic(Ortho {
+            left: -half_width,
+            right: half_width,
+            bottom: -half_height,
+            top: half_height,
+            near: NEAR,
+            far: FAR,
+          })
+        },
+      };
+      *camera = Camera::from(projection);
+    }
+  }
+}
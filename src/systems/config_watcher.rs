@@ -0,0 +1,189 @@
+use std::{
+  fs,
+  time::SystemTime,
+};
+
+use amethyst::{
+  core::Time,
+  ecs::prelude::*,
+};
+
+use ::{
+  config::{
+    CameraConfig,
+    GameConfig,
+    LevelsConfig,
+    PhysicsConfig,
+    SoundConfig,
+    SpawnerConfig,
+    game_config_path,
+    load_game_config,
+  },
+  resources::{
+    AudioSettings,
+    Command,
+    CommandChannel,
+    GameMode,
+    Level,
+    LoadState,
+    PhysicsWorld,
+    Sounds,
+  },
+  systems::override_camera_config,
+};
+
+//How often (in seconds) to check if the config file has been modified
+const POLL_INTERVAL: f32 = 1.0;
+
+///Watches resources/config.ron and pushes any sections that changed into their resources.
+///The current level is only restarted when its own definition changed.
+#[derive(Default)]
+pub struct ConfigWatcher {
+  elapsed: f32,
+  modified: Option<SystemTime>,
+  //The config as it was last loaded from the file, changes are worked out against this rather than
+  //the resources since the level overrides some of them
+  config: Option<GameConfig>,
+}
+
+impl<'s> System<'s> for ConfigWatcher {
+  type SystemData = (
+    Read<'s, Time>,
+    Read<'s, Level>,
+    Read<'s, LazyUpdate>,
+  );
+
+  fn setup(&mut self, res: &mut Resources) {
+    Self::SystemData::setup(res);
+    self.modified = modified_time();
+    self.config = load_game_config().ok();
+  }
+
+  fn run(&mut self, (time, level, updater): Self::SystemData) {
+    self.elapsed += time.delta_seconds();
+    //Anything changed in the meantime is picked up once the editor closes
+    if self.elapsed < POLL_INTERVAL || level.editing {
+      return;
+    }
+    self.elapsed = 0.0;
+
+    let modified = modified_time();
+    if modified == self.modified {
+      return;
+    }
+    self.modified = modified;
+
+    //Editors often save in several steps so a half written file is expected now and then, the next save will fix it
+    let new_config = match load_game_config() {
+      Ok(config) => config,
+      Err(e) => {
+        error!("Failed to reload GameConfig: {}", e);
+        return;
+      },
+    };
+
+    if let Some(ref old_config) = self.config {
+      if new_config.physics != old_config.physics {
+        let physics = new_config.physics.clone();
+        updater.exec_mut(move |world| reload_physics(world, physics));
+      }
+      if new_config.camera != old_config.camera {
+        let camera = new_config.camera.clone();
+        updater.exec_mut(move |world| reload_camera(world, camera));
+      }
+      if new_config.spawner != old_config.spawner {
+        let spawner = new_config.spawner.clone();
+        updater.exec_mut(move |world| reload_spawner(world, spawner));
+      }
+      if new_config.sound != old_config.sound {
+        let sound = new_config.sound.clone();
+        updater.exec_mut(move |world| reload_sound(world, sound));
+      }
+      if new_config.levels != old_config.levels {
+        let levels = new_config.levels.clone();
+        updater.exec_mut(move |world| reload_levels(world, levels));
+      }
+    }
+
+    self.config = Some(new_config);
+  }
+}
+
+fn modified_time() -> Option<SystemTime> {
+  fs::metadata(game_config_path())
+    .and_then(|metadata| metadata.modified())
+    .ok()
+}
+
+fn reload_physics(world: &mut World, physics: PhysicsConfig) {
+  info!("Physics config changed, reloading");
+  world.write_resource::<PhysicsWorld>().set_gravity(physics.gravity);
+  *world.write_resource::<PhysicsConfig>() = physics;
+}
+
+fn reload_camera(world: &mut World, mut camera: CameraConfig) {
+  info!("Camera config changed, reloading");
+  //Keep the loaded level's overrides on top of the new config
+  let mut level = world.write_resource::<Level>();
+  if level.load_state == LoadState::Loaded {
+    if let Some(overrides) = level.config().camera_overrides.clone() {
+      level.prev_camera_settings = Some(override_camera_config(&mut camera, &overrides));
+    }
+  }
+  *world.write_resource::<CameraConfig>() = camera;
+}
+
+fn reload_spawner(world: &mut World, spawner: SpawnerConfig) {
+  info!("Spawner config changed, reloading");
+  *world.write_resource::<SpawnerConfig>() = spawner;
+}
+
+fn reload_sound(world: &mut World, sound: SoundConfig) {
+  info!("Sound config changed, reloading");
+  //Changing the music tracks or sound effects needs a restart since they're loaded up front
+  //Volumes the player has set themselves stay as they are
+  if let Some(mut sounds) = world.res.try_fetch_mut::<Sounds>() {
+    world.read_resource::<AudioSettings>().apply(&sound, &mut sounds);
+  }
+  *world.write_resource::<SoundConfig>() = sound;
+}
+
+fn reload_levels(world: &mut World, mut levels: LevelsConfig) {
+  info!("Levels config changed, reloading");
+  if levels.levels.is_empty() {
+    error!("No levels defined in levels config, ignoring the change");
+    return;
+  }
+
+  let restart = {
+    let mut level = world.write_resource::<Level>();
+    //Taking the file's version would silently throw away what's been done in the editor
+    for &i in &level.unsaved_levels {
+      if let (Some(edited), Some(loaded)) = (level.levels.get(i), levels.levels.get_mut(i)) {
+        if edited != loaded {
+          warn!("Level {} has unsaved changes from the editor, keeping them instead of the config's version", i);
+          *loaded = edited.clone();
+        }
+      }
+    }
+
+    let current_level = level.current_level;
+    //Generated levels don't come from the config so there's nothing to restart for.
+    //Compared against the levels in use rather than the old config since unsaved edits are part of those
+    let restart = level.mode == GameMode::Campaign
+      && level.load_state == LoadState::Loaded
+      && level.levels.get(current_level) != levels.levels.get(current_level);
+
+    level.levels = levels.levels.clone();
+    level.current_level = current_level.min(level.levels.len() - 1);
+    restart
+  };
+  *world.write_resource::<LevelsConfig>() = levels;
+
+  if restart {
+    info!("Current level changed, restarting it");
+    world
+      .write_resource::<CommandChannel>()
+      .single_write(Command::RestartLevel);
+  }
+}
@@ -0,0 +1,98 @@
+use amethyst::{
+  ecs::prelude::*,
+  renderer::{
+    Material,
+    MeshHandle,
+    SpriteRender,
+  },
+};
+
+use ::{
+  components::{
+    Collider,
+    Direction,
+    Matriarch,
+    SpriteAnimation,
+    Walker,
+  },
+  resources::{
+    PhysicsWorld,
+    Sprites,
+    CREEP_FALL_ANIMATION,
+    CREEP_LAUNCH_ANIMATION,
+    CREEP_WALK_ANIMATION,
+    MATRIARCH_FALL_ANIMATION,
+    MATRIARCH_LAUNCH_ANIMATION,
+    MATRIARCH_WALK_ANIMATION,
+  },
+};
+
+//Vertical speed (m/s) above which a creep counts as launched or falling rather than walking
+const AIRBORNE_SPEED: f32 = 1.0;
+
+///Picks the walk, fall or launch animation for each creep from its velocity and faces it the way it's walking.
+///Creeps are swapped from cubes to sprites if there's a walk animation in the config.
+///The matriarch uses the matriarch animations so she can still be picked out once creeps lose their colours.
+#[derive(Default)]
+pub struct CreepAnimation;
+
+impl<'s> System<'s> for CreepAnimation {
+  type SystemData = (
+    Entities<'s>,
+    ReadStorage<'s, Walker>,
+    ReadStorage<'s, Collider>,
+    ReadStorage<'s, Matriarch>,
+    WriteStorage<'s, SpriteAnimation>,
+    WriteStorage<'s, SpriteRender>,
+    ReadStorage<'s, MeshHandle>,
+    Read<'s, PhysicsWorld>,
+    ReadExpect<'s, Sprites>,
+    Read<'s, LazyUpdate>,
+  );
+
+  fn run(&mut self, (entities, walkers, colliders, matriarchs, mut animations, mut renders, meshes, physics_world, sprites, updater): Self::SystemData) {
+    let walk_render = match sprites.first_frame(CREEP_WALK_ANIMATION) {
+      Some(render) => render,
+      None => return,
+    };
+
+    for (entity, _, _) in (&*entities, &walkers, !&animations).join() {
+      updater.insert(entity, SpriteAnimation::new(CREEP_WALK_ANIMATION));
+      updater.insert(entity, walk_render.clone());
+    }
+
+    //ShapeVisualizer might have given them a cube before they got a sprite
+    for (entity, _, _, _) in (&*entities, &walkers, &animations, &meshes).join() {
+      updater.remove::<MeshHandle>(entity);
+      updater.remove::<Material>(entity);
+    }
+
+    for (entity, walker, collider, animation, render) in (&*entities, &walkers, &colliders, &mut animations, &mut renders).join() {
+      let vertical_speed = physics_world
+        .body_state(collider.body_handle)
+        .map_or(0.0, |(_, velocity)| velocity.linear.y);
+
+      let (name, matriarch_name) = if vertical_speed > AIRBORNE_SPEED {
+        (CREEP_LAUNCH_ANIMATION, MATRIARCH_LAUNCH_ANIMATION)
+      } else if vertical_speed < -AIRBORNE_SPEED {
+        (CREEP_FALL_ANIMATION, MATRIARCH_FALL_ANIMATION)
+      } else {
+        (CREEP_WALK_ANIMATION, MATRIARCH_WALK_ANIMATION)
+      };
+      let name = if matriarchs.contains(entity) && sprites.animation(matriarch_name).is_some() {
+        matriarch_name
+      } else if sprites.animation(name).is_some() {
+        name
+      } else {
+        //Launch and fall are optional, walking's better than nothing
+        CREEP_WALK_ANIMATION
+      };
+      animation.play(name);
+
+      let flip = walker.direction == Direction::Left;
+      if render.flip_horizontal != flip {
+        render.flip_horizontal = flip;
+      }
+    }
+  }
+}
@@ -1,10 +1,6 @@
 use amethyst::{
+  core::cgmath::Vector2,
   ecs::prelude::*,
-  assets::AssetStorage,
-  audio::{
-    Source,
-    output::Output,
-  },
 };
 
 use ::{
@@ -16,7 +12,8 @@ use ::{
   resources::{
     PhysicsWorld,
     SpawnStats,
-    Sounds,
+    SoundChannel,
+    SoundEvent,
   },
 };
 
@@ -32,12 +29,10 @@ impl<'s> System<'s> for DeadlyArea {
     ReadStorage<'s, Collider>,
     Read<'s, PhysicsWorld>,
     Write<'s, SpawnStats>,
-    ReadExpect<'s, Sounds>,
-    Read<'s, AssetStorage<Source>>,
-    Option<Read<'s, Output>>,
+    Write<'s, SoundChannel>,
   );
 
-  fn run(&mut self, (entities, walkers, deadly_area_components, colliders, physics_world, mut spawn_stats, sounds, source_storage, output): Self::SystemData) {
+  fn run(&mut self, (entities, walkers, deadly_area_components, colliders, physics_world, mut spawn_stats, mut sound_events): Self::SystemData) {
     //Go through fetching all sensors and checking if walkers are in proximity
     for (_ec, sensor) in (&deadly_area_components, &colliders).join() {
       //Go through all other colliders in it's proximity
@@ -45,14 +40,19 @@ impl<'s> System<'s> for DeadlyArea {
         for prox in proxs {
           if let Some(entity) = physics_world.get_entity_for_collider(prox) {
             if let Some(_) = walkers.get(entity) {
+              let position = colliders
+                .get(entity)
+                .map(|c| Vector2::new(c.transform_next.translation.x, c.transform_next.translation.y));
+
               spawn_stats.killed += 1;
               entities
                 .delete(entity)
                 .expect("Failed to delete entity");
 
-              if let Some(output) = &output {
-                sounds.play_death(&source_storage, output);
-              }
+              sound_events.single_write(match position {
+                Some(position) => SoundEvent::at("death", position),
+                None => SoundEvent::new("death"),
+              });
             }
           }
         }
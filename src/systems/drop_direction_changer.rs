@@ -23,6 +23,8 @@ use ::{
     CommandChannel,
     PhysicsWorld,
     Sprites,
+    SpawnStats,
+    CHANGE_DIRECTION_SPRITE,
   },
 };
 
@@ -47,6 +49,7 @@ impl<'s> System<'s> for DropDirectionChanger {
     ReadExpect<'s, Sprites>,
     Read<'s, LazyUpdate>,
     ReadStorage<'s, Age>,
+    Write<'s, SpawnStats>,
   );
 
   fn setup(&mut self, res: &mut Resources) {
@@ -54,7 +57,7 @@ impl<'s> System<'s> for DropDirectionChanger {
     self.command_reader = Some(res.fetch_mut::<CommandChannel>().register_reader());
   }
 
-  fn run(&mut self, (entities, commands, matriarchs, transforms, mut walkers, change_direction_components, colliders, mut physics_world, physics_config, sprites, updater, ages): Self::SystemData) {
+  fn run(&mut self, (entities, commands, matriarchs, transforms, mut walkers, change_direction_components, colliders, mut physics_world, physics_config, sprites, updater, ages, mut spawn_stats): Self::SystemData) {
     let mut drop_direction_changer = false;
     for command in commands.read(self.command_reader.as_mut().unwrap()) {
       match command {
@@ -71,11 +74,10 @@ impl<'s> System<'s> for DropDirectionChanger {
             continue;
           }
           debug!("Dropping direction changer on Matriarch {:?}", e);
+          spawn_stats.abilities_used += 1;
 
           let direction = w.direction.reversed();
 
-          let sprite = sprites.change_direction.clone();
-
           let sensor = physics_world.create_ground_box_sensor(
             &Vector2::new(t.translation.x, t.translation.y), //Pos
             &Vector2::new(physics_config.change_direction_width * 0.5, physics_config.change_direction_height * 0.5), //Size
@@ -85,12 +87,17 @@ impl<'s> System<'s> for DropDirectionChanger {
             direction: direction,
           };
 
-          updater
+          let mut builder = updater
             .create_entity(&entities)
-            .with(sprite)
             .with(sensor)
-            .with(change_direction)
-            .build();
+            .with(change_direction);
+          if let Some(sprite) = sprites.get(CHANGE_DIRECTION_SPRITE) {
+            builder = builder.with(sprite);
+          }
+          if let Some(animation) = sprites.animate(CHANGE_DIRECTION_SPRITE) {
+            builder = builder.with(animation);
+          }
+          builder.build();
         }
       }
     }
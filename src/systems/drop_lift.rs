@@ -20,6 +20,8 @@ use ::{
     CommandChannel,
     PhysicsWorld,
     Sprites,
+    SpawnStats,
+    LIFT_SPRITE,
   },
 };
 
@@ -41,6 +43,7 @@ impl<'s> System<'s> for DropLift {
     ReadExpect<'s, Sprites>,
     Read<'s, LazyUpdate>,
     ReadStorage<'s, Age>,
+    Write<'s, SpawnStats>,
   );
 
   fn setup(&mut self, res: &mut Resources) {
@@ -48,7 +51,7 @@ impl<'s> System<'s> for DropLift {
     self.command_reader = Some(res.fetch_mut::<CommandChannel>().register_reader());
   }
 
-  fn run(&mut self, (entities, commands, matriarchs, transforms, walkers, mut physics_world, physics_config, sprites, updater, ages): Self::SystemData) {
+  fn run(&mut self, (entities, commands, matriarchs, transforms, walkers, mut physics_world, physics_config, sprites, updater, ages, mut spawn_stats): Self::SystemData) {
     let mut drop_lift = false;
     for command in commands.read(self.command_reader.as_mut().unwrap()) {
       match command {
@@ -66,22 +69,26 @@ impl<'s> System<'s> for DropLift {
           }
 
           debug!("Dropping lift on Matriarch {:?}", e);
+          spawn_stats.abilities_used += 1;
 
           let la = LaunchArea::new(w.direction);
 
-          let sprite = sprites.lift.clone();
-
           let sensor = physics_world.create_ground_box_sensor(
             &Vector2::new(t.translation.x, t.translation.y), //Pos
             &Vector2::new(physics_config.lift_width * 0.5, physics_config.lift_height * 0.5), //Size
             0.0);
 
-          updater
+          let mut builder = updater
             .create_entity(&entities)
             .with(la)
-            .with(sprite)
-            .with(sensor)
-            .build();
+            .with(sensor);
+          if let Some(sprite) = sprites.get(LIFT_SPRITE) {
+            builder = builder.with(sprite);
+          }
+          if let Some(animation) = sprites.animate(LIFT_SPRITE) {
+            builder = builder.with(animation);
+          }
+          builder.build();
         }
       }
     }
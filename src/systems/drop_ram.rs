@@ -25,6 +25,7 @@ use ::{
     Command,
     CommandChannel,
     PhysicsWorld,
+    SpawnStats,
   },
 };
 
@@ -48,6 +49,7 @@ impl<'s> System<'s> for DropRam {
     Read<'s, PhysicsConfig>,
     Read<'s, LazyUpdate>,
     ReadStorage<'s, Age>,
+    Write<'s, SpawnStats>,
   );
 
   fn setup(&mut self, res: &mut Resources) {
@@ -55,7 +57,7 @@ impl<'s> System<'s> for DropRam {
     self.command_reader = Some(res.fetch_mut::<CommandChannel>().register_reader());
   }
 
-  fn run(&mut self, (entities, commands, matriarchs, colors, shapes, walkers, mut physics_world, transforms, physics_config, updater, ages): Self::SystemData) {
+  fn run(&mut self, (entities, commands, matriarchs, colors, shapes, walkers, mut physics_world, transforms, physics_config, updater, ages, mut spawn_stats): Self::SystemData) {
     let mut drop_ram = false;
     for command in commands.read(self.command_reader.as_mut().unwrap()) {
       match command {
@@ -72,6 +74,7 @@ impl<'s> System<'s> for DropRam {
             continue;
           }
           debug!("Dropping ram on Matriarch {:?}", e);
+          spawn_stats.abilities_used += 1;
 
           let collider = physics_world.create_rigid_body_with_box_collider_with_density(
             &Vector2::new(t.translation.x, t.translation.y),
@@ -0,0 +1,127 @@
+use amethyst::{
+  controls::FlyControlTag,
+  core::transform::components::Transform,
+  ecs::prelude::*,
+};
+
+use ::{
+  components::{
+    Chunk,
+    Matriarch,
+    Walker,
+    Spawner,
+  },
+  resources::{
+    Level,
+    LoadState,
+    GameMode,
+    EndlessRun,
+    SpawnStats,
+  },
+  systems::create_level_objects,
+};
+
+//How far ahead of the camera terrain gets generated
+const LOOKAHEAD: f32 = 1200.0;
+//How far behind the camera a chunk has to be before it's removed
+const CLEANUP_DISTANCE: f32 = 800.0;
+//How close the camera or matriarch has to get to a chunk's spawner before it starts spawning
+const WAKE_DISTANCE: f32 = 400.0;
+//Anything that falls below this is dead, catches walkers that miss the pits
+const KILL_Y: f32 = -200.0;
+
+///Runs endless mode. Generates terrain ahead of the camera, removes it once it's far behind and tracks the distance.
+///Spawners in generated chunks sleep until the camera or matriarch gets near so creeps don't pile up out of sight.
+///Their creeps only count towards SpawnStats::total once they wake, otherwise a run where everyone died before
+///reaching one would never end.
+#[derive(Default)]
+pub struct Endless;
+
+impl<'s> System<'s> for Endless {
+  type SystemData = (
+    Entities<'s>,
+    ReadStorage<'s, Transform>,
+    ReadStorage<'s, FlyControlTag>,
+    ReadStorage<'s, Matriarch>,
+    ReadStorage<'s, Walker>,
+    ReadStorage<'s, Chunk>,
+    WriteStorage<'s, Spawner>,
+    Read<'s, Level>,
+    Write<'s, EndlessRun>,
+    Write<'s, SpawnStats>,
+    Read<'s, LazyUpdate>,
+  );
+
+  fn run(&mut self, (entities, transforms, fly_tags, matriarchs, walkers, chunks, mut spawners, level, mut endless_run, mut spawn_stats, updater): Self::SystemData) {
+    match level.mode {
+      GameMode::Endless(_) if level.load_state == LoadState::Loaded && level.outcome.is_none() => {},
+      _ => return,
+    }
+
+    for (transform, _matriarch) in (&transforms, &matriarchs).join() {
+      if transform.translation.x > endless_run.distance {
+        endless_run.distance = transform.translation.x;
+      }
+    }
+
+    for (entity, transform, _walker) in (&*entities, &transforms, &walkers).join() {
+      if transform.translation.y < KILL_Y {
+        spawn_stats.killed += 1;
+        entities
+          .delete(entity)
+          .expect("Failed to delete entity");
+      }
+    }
+
+    let camera_x = match (&transforms, &fly_tags).join().next() {
+      Some((transform, _)) => transform.translation.x,
+      None => return,
+    };
+
+    let matriarch_x = (&transforms, &matriarchs).join().next().map(|(transform, _)| transform.translation.x);
+    for (spawner, transform, _chunk) in (&mut spawners, &transforms, &chunks).join() {
+      let x = transform.translation.x;
+      let near = (x - camera_x).abs() < WAKE_DISTANCE || matriarch_x.map_or(false, |m| (x - m).abs() < WAKE_DISTANCE);
+      if spawner.dormant && near {
+        spawner.dormant = false;
+        spawn_stats.total += spawner.spawn_max;
+      }
+    }
+
+    //One chunk per frame is plenty to stay ahead
+    if endless_run.generator.generated_to() < camera_x + LOOKAHEAD {
+      let chunk = endless_run.generator.next_chunk();
+      let end_x = endless_run.generator.generated_to();
+      updater.exec_mut(move |world| {
+        let created = create_level_objects(world, &chunk);
+        let mut chunks = world.write_storage::<Chunk>();
+        let mut spawners = world.write_storage::<Spawner>();
+        let mut spawn_stats = world.write_resource::<SpawnStats>();
+        for entity in created {
+          chunks
+            .insert(entity, Chunk { end_x })
+            .expect("Failed to insert Chunk");
+          //Creating it added its creeps to the total, they're added back when it wakes
+          if let Some(spawner) = spawners.get_mut(entity) {
+            spawner.dormant = true;
+            spawn_stats.total -= spawner.spawn_max;
+          }
+        }
+      });
+    }
+
+    for (entity, chunk) in (&*entities, &chunks).join() {
+      if chunk.end_x < camera_x - CLEANUP_DISTANCE {
+        //Creeps that never got spawned aren't coming any more. Dormant ones were never counted
+        if let Some(spawner) = spawners.get(entity) {
+          if !spawner.dormant {
+            spawn_stats.total -= spawner.spawn_max.saturating_sub(spawner.spawn_count);
+          }
+        }
+        entities
+          .delete(entity)
+          .expect("Failed to delete entity");
+      }
+    }
+  }
+}
@@ -1,10 +1,6 @@
 use amethyst::{
+  core::cgmath::Vector2,
   ecs::prelude::*,
-  assets::AssetStorage,
-  audio::{
-    Source,
-    output::Output,
-  },
 };
 
 use ::{
@@ -16,7 +12,8 @@ use ::{
   resources::{
     PhysicsWorld,
     SpawnStats,
-    Sounds,
+    SoundChannel,
+    SoundEvent,
   },
 };
 
@@ -32,12 +29,10 @@ impl<'s> System<'s> for Exit {
     ReadStorage<'s, Collider>,
     Read<'s, PhysicsWorld>,
     Write<'s, SpawnStats>,
-    ReadExpect<'s, Sounds>,
-    Read<'s, AssetStorage<Source>>,
-    Option<Read<'s, Output>>,
+    Write<'s, SoundChannel>,
   );
 
-  fn run(&mut self, (entities, walkers, exit_components, colliders, physics_world, mut spawn_stats, sounds, source_storage, output): Self::SystemData) {
+  fn run(&mut self, (entities, walkers, exit_components, colliders, physics_world, mut spawn_stats, mut sound_events): Self::SystemData) {
     //Go through fetching all sensors and checking if walkers are in proximity
     for (_ec, sensor) in (&exit_components, &colliders).join() {
       //Go through all other colliders in it's proximity
@@ -45,15 +40,20 @@ impl<'s> System<'s> for Exit {
         for prox in proxs {
           if let Some(entity) = physics_world.get_entity_for_collider(prox) {
             if let Some(_) = walkers.get(entity) {
+              let position = colliders
+                .get(entity)
+                .map(|c| Vector2::new(c.transform_next.translation.x, c.transform_next.translation.y));
+
               spawn_stats.saved += 1;
 
               entities
                 .delete(entity)
                 .expect("Failed to delete entity");
 
-              if let Some(output) = &output {
-                sounds.play_exit(&source_storage, output);
-              }
+              sound_events.single_write(match position {
+                Some(position) => SoundEvent::at("exit", position),
+                None => SoundEvent::new("exit"),
+              });
             }
           }
         }
@@ -1,11 +1,6 @@
 use amethyst::{
   ecs::prelude::*,
   shrev::ReaderId,
-  assets::AssetStorage,
-  audio::{
-    Source,
-    output::Output,
-  },
 };
 
 use ::{
@@ -15,7 +10,8 @@ use ::{
   resources::{
     Command,
     CommandChannel,
-    Sounds,
+    SoundChannel,
+    SoundEvent,
   },
 };
 
@@ -29,9 +25,7 @@ impl<'s> System<'s> for Exodus {
   type SystemData = (
     Read<'s, CommandChannel>,
     WriteStorage<'s, Spawner>,
-    ReadExpect<'s, Sounds>,
-    Read<'s, AssetStorage<Source>>,
-    Option<Read<'s, Output>>,
+    Write<'s, SoundChannel>,
   );
 
   fn setup(&mut self, res: &mut Resources) {
@@ -39,7 +33,7 @@ impl<'s> System<'s> for Exodus {
     self.command_reader = Some(res.fetch_mut::<CommandChannel>().register_reader());
   }
 
-  fn run(&mut self, (commands, mut spawners, sounds, source_storage, output): Self::SystemData) {
+  fn run(&mut self, (commands, mut spawners, mut sound_events): Self::SystemData) {
     let mut exodus = false;
     for command in commands.read(self.command_reader.as_mut().unwrap()) {
       match command {
@@ -49,9 +43,7 @@ impl<'s> System<'s> for Exodus {
     }
 
     if exodus {
-      if let Some(output) = &output {
-        sounds.play_exodus(&source_storage, output);
-      }
+      sound_events.single_write(SoundEvent::new("exodus"));
       for s in (&mut spawners).join() {
         s.exodus = true;
       }
@@ -0,0 +1,130 @@
+use amethyst::{
+  core::transform::components::Transform,
+  ecs::prelude::*,
+  renderer::{
+    Hidden,
+    SpriteRender,
+    Transparent,
+  },
+};
+
+use ::{
+  components::{
+    Collider,
+    Matriarch,
+    Ghost as GhostComponent,
+  },
+  resources::{
+    Level,
+    LoadState,
+    GhostRun,
+    PhysicsWorld,
+    Sprites,
+    GHOST_SPRITE,
+  },
+};
+
+///Records the matriarch's position every physics step and plays back the best run as a ghost.
+///The ghost is just a see through creep sprite moved along the recorded track, it has no collider or walker.
+#[derive(Default)]
+pub struct Ghost;
+
+impl<'s> System<'s> for Ghost {
+  type SystemData = (
+    Entities<'s>,
+    ReadStorage<'s, Matriarch>,
+    ReadStorage<'s, Collider>,
+    ReadStorage<'s, GhostComponent>,
+    WriteStorage<'s, Transform>,
+    WriteStorage<'s, Hidden>,
+    WriteStorage<'s, SpriteRender>,
+    Read<'s, PhysicsWorld>,
+    Read<'s, Level>,
+    Write<'s, GhostRun>,
+    ReadExpect<'s, Sprites>,
+    Read<'s, LazyUpdate>,
+  );
+
+  fn run(&mut self, (entities, matriarchs, colliders, ghosts, mut transforms, mut hiddens, mut renders, physics_world, level, mut ghost_run, sprites, updater): Self::SystemData) {
+    if level.load_state != LoadState::Loaded {
+      //The level is going away, so should the ghost
+      for (entity, _) in (&*entities, &ghosts).join() {
+        entities
+          .delete(entity)
+          .expect("Failed to delete ghost");
+      }
+      return;
+    }
+
+    let GhostRun { ref mut recording, ref best, ref mut start_step } = *ghost_run;
+
+    let steps = physics_world.step_count();
+    let step = (steps - *start_step.get_or_insert(steps)) as usize;
+
+    //Record. If a frame took more than one step the position is repeated for each
+    if level.outcome.is_none() {
+      let position = (&matriarchs, &colliders)
+        .join()
+        .next()
+        .map(|(_, c)| (c.transform_next.translation.x, c.transform_next.translation.y));
+      while recording.points.len() < step {
+        recording.points.push(position);
+      }
+    }
+
+    //Playback
+    let best = match best {
+      Some(best) => best,
+      None => return,
+    };
+
+    let ghost = match (&*entities, &ghosts).join().next() {
+      Some((entity, _)) => entity,
+      None => {
+        let entity = entities.create();
+        updater.insert(entity, GhostComponent);
+        if let Some(sprite) = sprites.get(GHOST_SPRITE) {
+          updater.insert(entity, sprite);
+        }
+        updater.insert(entity, Transparent);
+        updater.insert(entity, Hidden);
+        updater.insert(entity, Transform::default());
+        return;
+      },
+    };
+
+    //Point n is the position after step n + 1. Lerp the same way the real matriarch is
+    let position = match (step.checked_sub(2).and_then(|s| best.get(s)), step.checked_sub(1).and_then(|s| best.get(s))) {
+      (Some(prev), Some(next)) => {
+        let alpha = physics_world.get_alpha();
+        Some((prev.0 + (next.0 - prev.0) * alpha, prev.1 + (next.1 - prev.1) * alpha))
+      },
+      (None, next) => next,
+      //The matriarch died between these steps, don't slide the ghost over to the new one
+      (Some(_), None) => None,
+    };
+
+    match position {
+      Some((x, y)) => {
+        if let Some(transform) = transforms.get_mut(ghost) {
+          //Face the way it's going like the creeps do
+          if let Some(render) = renders.get_mut(ghost) {
+            if x != transform.translation.x {
+              render.flip_horizontal = x < transform.translation.x;
+            }
+          }
+          transform.translation.x = x;
+          transform.translation.y = y;
+        }
+        hiddens.remove(ghost);
+      },
+      None => {
+        if !hiddens.contains(ghost) {
+          hiddens
+            .insert(ghost, Hidden)
+            .expect("Failed to hide ghost");
+        }
+      },
+    }
+  }
+}
@@ -1,12 +1,8 @@
 use std::cmp::PartialEq;
 
 use amethyst::{
+  core::cgmath::Vector2,
   ecs::prelude::*,
-  assets::AssetStorage,
-  audio::{
-    Source,
-    output::Output,
-  },
 };
 
 use nphysics2d::{
@@ -30,7 +26,8 @@ use ::{
   },
   resources::{
     PhysicsWorld,
-    Sounds,
+    SoundChannel,
+    SoundEvent,
   },
 };
 
@@ -58,13 +55,12 @@ impl<'s> System<'s> for LaunchArea {
     ReadStorage<'s, Collider>,
     Write<'s, PhysicsWorld>,
     Read<'s, PhysicsConfig>,
-    ReadExpect<'s, Sounds>,
-    Read<'s, AssetStorage<Source>>,
-    Option<Read<'s, Output>>,
+    Write<'s, SoundChannel>,
   );
 
-  fn run(&mut self, (walkers, mut launch_area_components, colliders, mut physics_world, physics_config, sounds, source_storage, output): Self::SystemData) {
+  fn run(&mut self, (walkers, mut launch_area_components, colliders, mut physics_world, physics_config, mut sound_events): Self::SystemData) {
     let mut to_launch = Vec::new();
+    let mut launched_at = Vec::new();
 
     //Go through fetching all sensors and checking if walkers are in proximity
     for (launch_area, sensor) in (&mut launch_area_components, &colliders).join() {
@@ -88,6 +84,9 @@ impl<'s> System<'s> for LaunchArea {
                 };
                 if !to_launch.contains(&tl) {
                   to_launch.push(tl);
+                  if let Some(c) = colliders.get(entity) {
+                    launched_at.push(Vector2::new(c.transform_next.translation.x, c.transform_next.translation.y));
+                  }
                 }
               }
             }
@@ -96,12 +95,10 @@ impl<'s> System<'s> for LaunchArea {
       }
     }
 
-    //No point playing the same sound multiple times in the same frame
-    if to_launch.len() > 0 {
-      if let Some(output) = &output {
-        sounds.play_lift(&source_storage, output);
-      }
+    //The dispatcher combines these into one sound
+    sound_events.iter_write(launched_at.into_iter().map(|position| SoundEvent::at("lift", position)));
 
+    if to_launch.len() > 0 {
       let velocity_left = Velocity::new(
         naVector2::new(
           physics_config.lift_velocity.x * -1.0,
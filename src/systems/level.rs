@@ -8,6 +8,8 @@ use amethyst::{
   controls::FlyControlTag,
 };
 
+use chrono::NaiveDate;
+
 use ::{
   config::{
     SpawnerConfig,
@@ -24,6 +26,24 @@ use ::{
     CommandChannel,
     Level as LevelResource,
     LoadState,
+    Simulation,
+    SaveData,
+    TimeAttack,
+    GhostRun,
+    GhostTrack,
+    RewindBuffer,
+    GameMode,
+    EndlessRun,
+    LevelState,
+    SavedMode,
+  },
+  generation::{
+    endless_start,
+    daily_level,
+  },
+  systems::{
+    capture_level_state,
+    restore_level_state,
   },
   components::{
     Color,
@@ -51,9 +71,11 @@ impl<'s> System<'s> for Level {
   type SystemData = (
     Read<'s, CommandChannel>,
     Read<'s, Time>,
+    Read<'s, Simulation>,
     Read<'s, LazyUpdate>,
     Write<'s, LevelResource>,
     Read<'s, SpawnStats>,
+    Write<'s, TimeAttack>,
   );
 
   fn setup(&mut self, res: &mut Resources) {
@@ -61,8 +83,16 @@ impl<'s> System<'s> for Level {
     self.command_reader = Some(res.fetch_mut::<CommandChannel>().register_reader());
   }
 
-  fn run(&mut self, (commands, time, updater, mut level_resource, spawn_stats): Self::SystemData) {
-    level_resource.runtime += time.delta_seconds();
+  fn run(&mut self, (commands, time, simulation, updater, mut level_resource, spawn_stats, mut time_attack): Self::SystemData) {
+    //The clocks stop as soon as the level is won or lost
+    if level_resource.outcome.is_none() {
+      let delta = simulation.delta(&time);
+      level_resource.runtime += delta;
+      if time_attack.active && !time_attack.finished && level_resource.load_state == LoadState::Loaded {
+        //The level clock stops while rewinding but the run's doesn't, otherwise rewind would be free time
+        time_attack.run_time += if simulation.rewinding { time.delta_seconds() } else { delta };
+      }
+    }
 
     let mut pending_action = true;
     match level_resource.load_state {
@@ -73,7 +103,7 @@ impl<'s> System<'s> for Level {
       //Without this, newly created colliders can collide with deleted ones for a
       //single frame and cause the simulation to try and separate them.
       LoadState::PhysicsCleanup => level_resource.load_state = LoadState::NeedLoad,
-      LoadState::Loaded => pending_action = false,
+      LoadState::Loaded | LoadState::Unloaded => pending_action = false,
     }
 
     if !pending_action {
@@ -81,23 +111,56 @@ impl<'s> System<'s> for Level {
       let mut next = false;
       let mut prev = false;
       let mut restart = false;
+      let mut load = None;
+      let mut start_mode = None;
+      let mut start_time_attack = false;
+      let mut unload = false;
+      let mut save_state = false;
+      let mut load_state = false;
       for command in commands.read(self.command_reader.as_mut().unwrap()) {
         match command {
           Command::ReloadLevels => reload = true,
           Command::RestartLevel => restart = true,
           Command::NextLevel => next = true,
           Command::PreviousLevel => prev = true,
+          Command::LoadLevel(index) => load = Some(*index),
+          Command::StartEndless(seed) => start_mode = Some(GameMode::Endless(*seed)),
+          Command::StartDaily(date) => start_mode = Some(GameMode::Daily(*date)),
+          Command::StartTimeAttack => start_time_attack = true,
+          Command::UnloadLevel => unload = true,
+          Command::SaveLevelState => save_state = true,
+          Command::LoadLevelState => load_state = true,
           _ => {},
         }
       }
 
-      if reload {
+      let campaign = level_resource.mode == GameMode::Campaign;
+
+      if start_time_attack {
+        updater.exec_mut(move |world| {
+          change_level(world, GameMode::Campaign, 0);
+          world.write_resource::<TimeAttack>().start();
+        });
+      } else if load_state {
+        updater.exec_mut(move |world| load_level_state(world));
+      } else if let Some(index) = load {
+        updater.exec_mut(move |world| change_level(world, GameMode::Campaign, index));
+      } else if let Some(mode) = start_mode {
+        let index = level_resource.current_level;
+        updater.exec_mut(move |world| change_level(world, mode, index));
+      } else if level_resource.load_state == LoadState::Unloaded {
+        //Nothing else makes sense until a level has been loaded
+      } else if unload {
+        updater.exec_mut(move |world| unload_to_menu(world));
+      } else if save_state {
+        updater.exec_mut(move |world| save_level_state(world));
+      } else if reload {
         updater.exec_mut(move |world| reload_config(world));
-      } else if next {
+      } else if next && campaign {
         if spawn_stats.saved_ratio() >= spawn_stats.win_ratio {
           updater.exec_mut(move |world| next_level(world));
         }
-      } else if prev {
+      } else if prev && campaign && !time_attack.active {
         updater.exec_mut(move |world| prev_level(world));
       } else if restart {
         updater.exec_mut(move |world| restart_level(world));
@@ -132,11 +195,15 @@ fn load_config(world: &mut World) {
   //Jump to  the start level if it's higher
   level_resource.current_level = level_resource.current_level.max(start_level);
 
-  //This will trigger load on the next frame
-  level_resource.load_state = LoadState::NeedLoad;
+  //Levels up to the start level are always available
+  let mut save_data = world.write_resource::<SaveData>();
+  save_data.unlocked = save_data.unlocked.max(start_level);
+
+  //Nothing gets loaded until something sends a LoadLevel command
+  level_resource.load_state = LoadState::Unloaded;
 }
 
-//Creates the entities associated with LevelResource.levels[LevelResource.current_level]
+//Creates the entities associated with LevelResource.levels[LevelResource.current_level] (or the generated level)
 fn load_level(world: &mut World) {
   if LoadState::NeedLoad != world.read_resource::<LevelResource>().load_state {
     panic!("load_level called but load_state wasn't NeedLoad");
@@ -150,15 +217,46 @@ fn load_level(world: &mut World) {
     *stats = SpawnStats::default();
   }
 
+  let mut runtime = 0.0;
   let prev_cam = {
     //TODO: must be a better way than the clone
     //Clone because level_resource is borrowed from world and we also need to mutate world
     let level = {
+      let mut level_resource = world.write_resource::<LevelResource>();
+      let level = match level_resource.mode {
+        GameMode::Campaign => level_resource.levels[level_resource.current_level].clone(),
+        GameMode::Endless(seed) => endless_start(seed),
+        GameMode::Daily(date) => daily_level(date),
+      };
+      level_resource.loaded_config = Some(level.clone());
+      level
+    };
+
+    let (mode, current_level) = {
       let level_resource = world.read_resource::<LevelResource>();
-      level_resource.levels[level_resource.current_level].clone()
+      (level_resource.mode, level_resource.current_level)
     };
-    //Create the level contents
-    create_level_objects(world, &level);
+    if let GameMode::Endless(seed) = mode {
+      *world.write_resource::<EndlessRun>() = EndlessRun::new(seed);
+    }
+    //Ghosts are only kept for the hand made levels, generated ones change too much
+    *world.write_resource::<GhostRun>() = GhostRun::new(match mode {
+      GameMode::Campaign => GhostTrack::load_best(current_level),
+      _ => None,
+    });
+    *world.write_resource::<RewindBuffer>() = RewindBuffer::default();
+
+    //Create the level contents, either fresh or from a saved state
+    let pending_state = world.write_resource::<LevelResource>().pending_state.take();
+    match pending_state {
+      Some(state) => {
+        restore_level_state(world, &state);
+        runtime = state.runtime;
+      },
+      None => {
+        create_level_objects(world, &level);
+      },
+    }
 
     //Update the camera if there are overrides
     if let Some(camera_overrides) = &level.camera_overrides {
@@ -172,7 +270,8 @@ fn load_level(world: &mut World) {
   let mut level_resource = world.write_resource::<LevelResource>();
   level_resource.prev_camera_settings = prev_cam;
   level_resource.load_state = LoadState::Loaded;
-  level_resource.runtime = 0.0;
+  level_resource.runtime = runtime;
+  level_resource.outcome = None;
 }
 
 //Cleans up anything we've created
@@ -209,11 +308,33 @@ fn unload_level(world: &mut World) {
   level_resource.load_state = LoadState::PhysicsCleanup;
 }
 
+//Like unloading for a restart but nothing's loaded afterwards. The physics cleanup frame isn't needed since
+//loading another level takes a LoadLevel (or similar) command from the menu
+fn unload_to_menu(world: &mut World) {
+  unload_level(world);
+  world.write_resource::<TimeAttack>().stop();
+
+  let mut level_resource = world.write_resource::<LevelResource>();
+  level_resource.load_state = LoadState::Unloaded;
+  level_resource.outcome = None;
+}
+
 fn restart_level(world: &mut World) {
   unload_level(world);
 }
 
 fn next_level(world: &mut World) {
+  //The win itself is recorded by LevelOutcome, moving on only unlocks the following campaign level
+  {
+    let level_resource = world.read_resource::<LevelResource>();
+    let mut save_data = world.write_resource::<SaveData>();
+    let next = level_resource.current_level + 1;
+    if level_resource.mode == GameMode::Campaign && next < level_resource.levels.len() && save_data.unlocked < next {
+      save_data.unlocked = next;
+      save_data.save();
+    }
+  }
+
   if {
     let mut level_resource = world.write_resource::<LevelResource>();
     if level_resource.current_level < level_resource.levels.len() - 1 {
@@ -241,6 +362,63 @@ fn prev_level(world: &mut World) {
   }
 }
 
+fn change_level(world: &mut World, mode: GameMode, index: usize) {
+  let loaded = {
+    let mut level_resource = world.write_resource::<LevelResource>();
+    if index >= level_resource.levels.len() {
+      error!("Tried to load level {} but there are only {} levels", index, level_resource.levels.len());
+      return;
+    }
+    level_resource.current_level = index;
+    level_resource.mode = mode;
+    level_resource.load_state == LoadState::Loaded
+  };
+
+  //Jumping to a level ends any time attack run, StartTimeAttack starts a new one after this
+  world.write_resource::<TimeAttack>().stop();
+
+  if loaded {
+    unload_level(world);
+  } else {
+    world.write_resource::<LevelResource>().load_state = LoadState::NeedLoad;
+  }
+}
+
+fn save_level_state(world: &mut World) {
+  if let Some(state) = capture_level_state(world) {
+    state.save();
+  }
+}
+
+//Switches to the saved level and has load_level restore it instead of starting fresh
+fn load_level_state(world: &mut World) {
+  let state = match LevelState::load_user() {
+    Some(state) => state,
+    None => return,
+  };
+
+  let (mode, index) = match state.mode {
+    SavedMode::Campaign(index) => (GameMode::Campaign, index),
+    SavedMode::Daily(ref date) => match NaiveDate::parse_from_str(date, "%Y-%m-%d") {
+      Ok(date) => (GameMode::Daily(date), world.read_resource::<LevelResource>().current_level),
+      Err(e) => {
+        error!("Invalid date {:?} in level state: {}", date, e);
+        return;
+      },
+    },
+  };
+
+  {
+    let mut level_resource = world.write_resource::<LevelResource>();
+    if index >= level_resource.levels.len() {
+      error!("Level state is for level {} but there are only {} levels", index, level_resource.levels.len());
+      return;
+    }
+    level_resource.pending_state = Some(state);
+  }
+  change_level(world, mode, index);
+}
+
 //Attempts to load LevelsConfig from the filesystem and push it into the LevelsConfig resource is load succeeds
 fn reload_config(world: &mut World) {
   if LoadState::Loaded != world.read_resource::<LevelResource>().load_state {
@@ -258,15 +436,25 @@ fn reload_config(world: &mut World) {
       //Clear up the current level
       unload_level(world);
 
-      //Trigger a config load on next frame
-      let mut level_resource = world.write_resource::<LevelResource>();
-      level_resource.load_state = LoadState::NeedConfig;
+      //Trigger a config load on next frame then load the same level again once that's done
+      let reload_command = {
+        let mut level_resource = world.write_resource::<LevelResource>();
+        level_resource.load_state = LoadState::NeedConfig;
+        match level_resource.mode {
+          GameMode::Campaign => Command::LoadLevel(level_resource.current_level),
+          GameMode::Endless(seed) => Command::StartEndless(seed),
+          GameMode::Daily(date) => Command::StartDaily(date),
+        }
+      };
+      world
+        .write_resource::<CommandChannel>()
+        .single_write(reload_command);
     },
     Err(e) => error!("Error loading GameConfig: {}", e),
   }
 }
 
-fn create_object(world: &mut World, width: f32, height: f32, x: f32, y: f32, otype: ObjectType, color: Option<Color>, rotation: Option<f32>, add_extras: Option<&Fn(EntityBuilder) -> EntityBuilder>) {
+fn create_object(world: &mut World, width: f32, height: f32, x: f32, y: f32, otype: ObjectType, color: Option<Color>, rotation: Option<f32>, add_extras: Option<&Fn(EntityBuilder) -> EntityBuilder>) -> Entity {
   let object = {
     let mut physics_world = world.write_resource::<PhysicsWorld>();
     match otype {
@@ -299,10 +487,10 @@ fn create_object(world: &mut World, width: f32, height: f32, x: f32, y: f32, oty
     builder = add_extras(builder);
   }
 
-  builder.build();
+  builder.build()
 }
 
-fn create_wall(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>) {
+fn create_wall(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>) -> Entity {
   create_object(
     world,
     width,
@@ -313,10 +501,10 @@ fn create_wall(world: &mut World, width: f32, height: f32, x: f32, y: f32, color
     color,
     rotation,
     None
-  );
+  )
 }
 
-fn create_hazard(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>) {
+fn create_hazard(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>) -> Entity {
   create_object(
     world,
     width,
@@ -327,10 +515,10 @@ fn create_hazard(world: &mut World, width: f32, height: f32, x: f32, y: f32, col
     color,
     rotation,
     Some(&|builder| builder.with(DeadlyArea)),
-  );
+  )
 }
 
-fn create_exit(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>) {
+fn create_exit(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>) -> Entity {
   create_object(
     world,
     width,
@@ -341,10 +529,10 @@ fn create_exit(world: &mut World, width: f32, height: f32, x: f32, y: f32, color
     color,
     rotation,
     Some(&|builder| builder.with(Exit)),
-  );
+  )
 }
 
-fn create_block(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>) {
+fn create_block(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>) -> Entity {
   create_object(
     world,
     width,
@@ -355,10 +543,10 @@ fn create_block(world: &mut World, width: f32, height: f32, x: f32, y: f32, colo
     color,
     rotation,
     None,
-  );
+  )
 }
 
-fn create_spawner(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>, freq: f32, max: u32) {
+fn create_spawner(world: &mut World, width: f32, height: f32, x: f32, y: f32, color: Option<Color>, rotation: Option<f32>, freq: f32, max: u32) -> Entity {
   world
     .write_resource::<SpawnStats>()
     .total += max;
@@ -379,13 +567,16 @@ fn create_spawner(world: &mut World, width: f32, height: f32, x: f32, y: f32, co
       });
       builder.with(spawner)
     }),
-  );
+  )
 }
 
-fn create_level_objects(world: &mut World, level: &LevelConfig) {
+///Creates the entities for everything in the level and returns them
+pub fn create_level_objects(world: &mut World, level: &LevelConfig) -> Vec<Entity> {
+  let mut created = Vec::new();
+
   if let Some(ref set) = level.walls {
     for o in &set.list {
-      create_wall(
+      created.push(create_wall(
         world,
         o.size.x,
         o.size.y,
@@ -393,13 +584,13 @@ fn create_level_objects(world: &mut World, level: &LevelConfig) {
         o.position.y,
         o.color.or(set.color),
         o.rotation,
-      );
+      ));
     }
   }
 
   if let Some(ref set) = level.deadly_areas {
     for o in &set.list {
-      create_hazard(
+      created.push(create_hazard(
         world,
         o.size.x,
         o.size.y,
@@ -407,13 +598,13 @@ fn create_level_objects(world: &mut World, level: &LevelConfig) {
         o.position.y,
         o.color.or(set.color),
         o.rotation,
-      );
+      ));
     }
   }
 
   if let Some(ref set) = level.exits {
     for o in &set.list {
-      create_exit(
+      created.push(create_exit(
         world,
         o.size.x,
         o.size.y,
@@ -421,7 +612,7 @@ fn create_level_objects(world: &mut World, level: &LevelConfig) {
         o.position.y,
         o.color.or(set.color),
         o.rotation,
-      );
+      ));
     }
   }
 
@@ -441,7 +632,7 @@ fn create_level_objects(world: &mut World, level: &LevelConfig) {
     }
 
     for o in &set.list {
-      create_spawner(
+      created.push(create_spawner(
         world,
         o.size.x,
         o.size.y,
@@ -451,13 +642,13 @@ fn create_level_objects(world: &mut World, level: &LevelConfig) {
         o.rotation,
         freq,
         max,
-      );
+      ));
     }
   }
 
   if let Some(ref set) = level.blocks {
     for o in &set.list {
-      create_block(
+      created.push(create_block(
         world,
         o.size.x,
         o.size.y,
@@ -465,13 +656,42 @@ fn create_level_objects(world: &mut World, level: &LevelConfig) {
         o.position.y,
         o.color.or(set.color),
         o.rotation,
-      );
+      ));
     }
   }
+
+  created
 }
 
 fn update_camera(world: &mut World, overrides: &CameraOverrides) -> CameraOverrides {
   let mut camera_config = world.write_resource::<CameraConfig>();
+  let prev = override_camera_config(&mut camera_config, overrides);
+
+  //NOTE: This relies on the camera prefab (created by MainMenuState) having finished loading.
+  //It has by the time the player has picked a level but won't be if a level is loaded immediately on startup.
+  if let Some(position) = &overrides.position {
+    for (t, _) in (
+      &mut world.write_storage::<Transform>(),
+      &world.read_storage::<FlyControlTag>()).join() {
+
+      t.translation = *position;
+    }
+  } else {
+    //If position isn't specified, just reset the zoom
+    for (t, _) in (
+      &mut world.write_storage::<Transform>(),
+      &world.read_storage::<FlyControlTag>()).join() {
+
+      t.translation.z = camera_config.z_default;
+    }
+  }
+
+  prev
+}
+
+///Applies the overrides to the camera config, returns the settings needed to undo them.
+///Position isn't part of the config so that's left to the caller.
+pub fn override_camera_config(camera_config: &mut CameraConfig, overrides: &CameraOverrides) -> CameraOverrides {
   let prev = CameraOverrides {
     convergence_speed: Some(camera_config.convergence_speed),
     offset: Some(camera_config.offset),
@@ -492,25 +712,5 @@ fn update_camera(world: &mut World, overrides: &CameraOverrides) -> CameraOverri
     camera_config.final_position = *final_position;
   }
 
-  //TODO: This doesn't work on the first level because the FlyControlTag hasn't been initialized
-  //due to async prefab loading in RunningState
-  //It's not really an issue since the prefab sets the pos for the first level anyway.
-  if let Some(position) = &overrides.position {
-    for (t, _) in (
-      &mut world.write_storage::<Transform>(),
-      &world.read_storage::<FlyControlTag>()).join() {
-
-      t.translation = *position;
-    }
-  } else {
-    //If position isn't specified, just reset the zoom
-    for (t, _) in (
-      &mut world.write_storage::<Transform>(),
-      &world.read_storage::<FlyControlTag>()).join() {
-
-      t.translation.z = camera_config.z_default;
-    }
-  }
-
   prev
 }
\ No newline at end of file
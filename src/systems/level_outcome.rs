@@ -0,0 +1,109 @@
+use amethyst::ecs::prelude::*;
+
+use ::resources::{
+  SpawnStats,
+  Level,
+  LoadState,
+  Outcome,
+  SaveData,
+  GameMode,
+  EndlessRun,
+  DailyHistory,
+  daily_score,
+  TimeAttack,
+  GhostRun,
+};
+
+///Watches the spawn stats to decide when a level has been won or can no longer be won.
+#[derive(Default)]
+pub struct LevelOutcome;
+
+impl<'s> System<'s> for LevelOutcome {
+  type SystemData = (
+    Read<'s, SpawnStats>,
+    Write<'s, Level>,
+    Write<'s, SaveData>,
+    Read<'s, EndlessRun>,
+    Write<'s, DailyHistory>,
+    Write<'s, TimeAttack>,
+    Read<'s, GhostRun>,
+  );
+
+  fn run(&mut self, (spawn_stats, mut level, mut save_data, endless_run, mut daily_history, mut time_attack, ghost_run): Self::SystemData) {
+    if level.load_state != LoadState::Loaded || level.outcome.is_some() || spawn_stats.total == 0 {
+      return;
+    }
+
+    //Endless runs can't be won, they just go on until everyone is gone
+    if let GameMode::Endless(seed) = level.mode {
+      if spawn_stats.remaining() == 0 {
+        let score = endless_run.score(0);
+        info!("Endless run (seed {}) over after {}m, score {}", seed, endless_run.distance_meters(), score);
+        if score > save_data.best_endless_score {
+          save_data.best_endless_score = score;
+          save_data.save();
+        }
+        level.outcome = Some(Outcome::Lost);
+      }
+      return;
+    }
+
+    let outcome = outcome(&spawn_stats);
+
+    //Daily results go in their own history rather than the campaign progress
+    if let GameMode::Daily(date) = level.mode {
+      if let Some(outcome) = outcome {
+        let won = outcome == Outcome::Won;
+        let score = daily_score(spawn_stats.saved, level.runtime);
+        info!("Daily challenge {} {} with score {}", date, if won { "won" } else { "lost" }, score);
+        daily_history.record(date, won, score);
+        daily_history.save();
+      }
+      level.outcome = outcome;
+      return;
+    }
+
+    if outcome == Some(Outcome::Won) {
+      let stars = level.config()
+        .scoring()
+        .stars(&spawn_stats, level.runtime);
+      info!("Level {} won in {:.*}s with {} stars", level.current_level, 1, level.runtime, stars);
+      if save_data.record_win(level.current_level, &spawn_stats, level.runtime, stars) {
+        ghost_run.recording.save_best(level.current_level);
+      }
+
+      if time_attack.active {
+        time_attack.split(level.current_level);
+        save_data.time_attack.record_level(level.current_level, level.runtime);
+
+        if level.current_level + 1 >= level.levels.len() {
+          time_attack.finished = true;
+          if save_data.time_attack.record_run(&time_attack.splits) {
+            info!("New best time attack run: {:.*}s", 2, time_attack.run_time);
+          }
+        }
+      }
+
+      save_data.save();
+    } else if outcome == Some(Outcome::Lost) {
+      info!("Level {} lost", level.current_level);
+    }
+
+    level.outcome = outcome;
+  }
+}
+
+fn outcome(spawn_stats: &SpawnStats) -> Option<Outcome> {
+  let total = spawn_stats.total as f32;
+  let resolved = spawn_stats.saved + spawn_stats.killed;
+  let remaining = spawn_stats.total.saturating_sub(resolved);
+
+  //Even if every creep that's left makes it out it won't be enough
+  if (spawn_stats.saved + remaining) as f32 / total < spawn_stats.win_ratio {
+    Some(Outcome::Lost)
+  } else if remaining == 0 && spawn_stats.saved_ratio() >= spawn_stats.win_ratio {
+    Some(Outcome::Won)
+  } else {
+    None
+  }
+}
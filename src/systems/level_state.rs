@@ -0,0 +1,235 @@
+use amethyst::{
+  core::cgmath::Vector2,
+  ecs::prelude::*,
+};
+
+use nalgebra::{
+  Isometry2,
+  Vector2 as naVector2,
+};
+
+use nphysics2d::math::Velocity;
+
+use ncollide2d::shape::Cuboid;
+
+use ::{
+  components::{
+    Age,
+    ChangeDirection,
+    Collider,
+    Color,
+    ConstantVelocity,
+    DeadlyArea,
+    Exit,
+    Family,
+    LaunchArea,
+    Matriarch,
+    Spawner,
+    Walker,
+  },
+  config::PhysicsConfig,
+  resources::{
+    GameMode,
+    Level,
+    LevelState,
+    PhysicsWorld,
+    SavedColliderKind,
+    SavedEntity,
+    SavedMode,
+    SpawnStats,
+    Sprites,
+    LIFT_SPRITE,
+    CHANGE_DIRECTION_SPRITE,
+    SCALE_METERS_PER_PIXEL,
+    SCALE_PIXELS_PER_METER,
+  },
+  systems::creep_collision_groups,
+};
+
+///Captures every collider entity in the level along with the Level and SpawnStats resources.
+///Endless runs can't be saved since the terrain generator isn't part of the state.
+pub fn capture_level_state(world: &mut World) -> Option<LevelState> {
+  let (mode, runtime) = {
+    let level = world.read_resource::<Level>();
+    let mode = match level.mode {
+      GameMode::Campaign => SavedMode::Campaign(level.current_level),
+      GameMode::Daily(date) => SavedMode::Daily(date.format("%Y-%m-%d").to_string()),
+      GameMode::Endless(_) => {
+        warn!("Endless runs can't be saved");
+        return None;
+      },
+    };
+    (mode, level.runtime)
+  };
+
+  let physics_world = world.read_resource::<PhysicsWorld>();
+  let ram_density = world.read_resource::<PhysicsConfig>().ram_density;
+  let entities = world.entities();
+  let colliders = world.read_storage::<Collider>();
+  let colors = world.read_storage::<Color>();
+  let walkers = world.read_storage::<Walker>();
+  let ages = world.read_storage::<Age>();
+  let matriarchs = world.read_storage::<Matriarch>();
+  let launch_areas = world.read_storage::<LaunchArea>();
+  let change_directions = world.read_storage::<ChangeDirection>();
+  let spawners = world.read_storage::<Spawner>();
+  let constant_velocities = world.read_storage::<ConstantVelocity>();
+  let deadly_areas = world.read_storage::<DeadlyArea>();
+  let exits = world.read_storage::<Exit>();
+
+  let mut saved = Vec::new();
+  for (entity, c) in (&*entities, &colliders).join() {
+    let collider = match physics_world.world.collider(c.collider_handle) {
+      Some(collider) => collider,
+      None => continue,
+    };
+
+    let size = match collider.shape().as_ref().as_shape::<Cuboid<f32>>() {
+      Some(cuboid) => {
+        let he = cuboid.half_extents();
+        let margin = collider.data().margin();
+        ((he.x + margin) * 2.0 * SCALE_PIXELS_PER_METER, (he.y + margin) * 2.0 * SCALE_PIXELS_PER_METER)
+      },
+      None => {
+        warn!("Skipping non cuboid collider {:?} while saving level state", entity);
+        continue;
+      },
+    };
+
+    let sensor = launch_areas.contains(entity)
+      || change_directions.contains(entity)
+      || spawners.contains(entity)
+      || deadly_areas.contains(entity)
+      || exits.contains(entity);
+
+    let (kind, velocity) = if !c.body_handle.is_ground() {
+      let density = if constant_velocities.contains(entity) { ram_density } else { 1.0 };
+      let velocity = physics_world
+        .body_state(c.body_handle)
+        .map(|(_, v)| (v.linear.x, v.linear.y, v.angular));
+      (SavedColliderKind::Body { density }, velocity)
+    } else if sensor {
+      (SavedColliderKind::Sensor, None)
+    } else {
+      (SavedColliderKind::Ground, None)
+    };
+
+    let position = collider.position();
+    saved.push(SavedEntity {
+      kind,
+      size,
+      position: (position.translation.vector.x * SCALE_PIXELS_PER_METER, position.translation.vector.y * SCALE_PIXELS_PER_METER),
+      rotation: position.rotation.angle(),
+      velocity,
+      color: colors.get(entity).cloned(),
+      walker: walkers.get(entity).cloned(),
+      age: ages.get(entity).cloned(),
+      matriarch: matriarchs.get(entity).cloned(),
+      launch_area: launch_areas.get(entity).map(|la| la.direction),
+      change_direction: change_directions.get(entity).cloned(),
+      spawner: spawners.get(entity).cloned(),
+      constant_velocity: constant_velocities.get(entity).map(|cv| (cv.velocity.linear.x, cv.velocity.linear.y, cv.velocity.angular)),
+      deadly_area: deadly_areas.contains(entity),
+      exit: exits.contains(entity),
+    });
+  }
+
+  Some(LevelState {
+    mode,
+    runtime,
+    spawn_stats: world.read_resource::<SpawnStats>().clone(),
+    entities: saved,
+  })
+}
+
+///Recreates the entities from a saved state. The level should already be unloaded.
+pub fn restore_level_state(world: &mut World, state: &LevelState) {
+  for saved in &state.entities {
+    let position = Vector2::new(saved.position.0, saved.position.1);
+    let size = Vector2::new(saved.size.0, saved.size.1);
+
+    let collider = {
+      let mut physics_world = world.write_resource::<PhysicsWorld>();
+      match saved.kind {
+        SavedColliderKind::Ground => physics_world.create_ground_box_collider(&position, &size, saved.rotation),
+        //The sensor and body creation functions flip the rotation
+        SavedColliderKind::Sensor => physics_world.create_ground_box_sensor(&position, &size, -saved.rotation),
+        SavedColliderKind::Body { density } => {
+          let collider = physics_world.create_rigid_body_with_box_collider_with_density(&position, &size, -saved.rotation, density);
+          if let Some((x, y, angular)) = saved.velocity {
+            let isometry = Isometry2::new(
+              naVector2::new(saved.position.0 * SCALE_METERS_PER_PIXEL, saved.position.1 * SCALE_METERS_PER_PIXEL),
+              saved.rotation);
+            physics_world.set_body_state(collider.body_handle, &isometry, &Velocity::new(naVector2::new(x, y), angular));
+          }
+          if saved.walker.is_some() {
+            physics_world
+              .world
+              .collision_world_mut()
+              .set_collision_groups(collider.collider_handle, creep_collision_groups());
+          }
+          collider
+        },
+      }
+    };
+
+    let (lift_sprite, lift_animation, change_direction_sprite, change_direction_animation) = {
+      let sprites = world.read_resource::<Sprites>();
+      (sprites.get(LIFT_SPRITE), sprites.animate(LIFT_SPRITE), sprites.get(CHANGE_DIRECTION_SPRITE), sprites.animate(CHANGE_DIRECTION_SPRITE))
+    };
+    let mut builder = world
+      .create_entity()
+      .with(collider);
+
+    if let Some(color) = saved.color {
+      builder = builder.with(color);
+    }
+    if let Some(ref walker) = saved.walker {
+      builder = builder
+        .with(walker.clone())
+        .with(Family::default());
+    }
+    if let Some(age) = saved.age {
+      builder = builder.with(age);
+    }
+    if let Some(ref matriarch) = saved.matriarch {
+      builder = builder.with(matriarch.clone());
+    }
+    if let Some(direction) = saved.launch_area {
+      builder = builder.with(LaunchArea::new(direction));
+      if let Some(sprite) = lift_sprite {
+        builder = builder.with(sprite);
+      }
+      if let Some(animation) = lift_animation {
+        builder = builder.with(animation);
+      }
+    }
+    if let Some(ref change_direction) = saved.change_direction {
+      builder = builder.with(change_direction.clone());
+      if let Some(sprite) = change_direction_sprite {
+        builder = builder.with(sprite);
+      }
+      if let Some(animation) = change_direction_animation {
+        builder = builder.with(animation);
+      }
+    }
+    if let Some(ref spawner) = saved.spawner {
+      builder = builder.with(spawner.clone());
+    }
+    if let Some((x, y, angular)) = saved.constant_velocity {
+      builder = builder.with(ConstantVelocity {
+        velocity: Velocity::new(naVector2::new(x, y), angular),
+      });
+    }
+    if saved.deadly_area {
+      builder = builder.with(DeadlyArea);
+    }
+    if saved.exit {
+      builder = builder.with(Exit);
+    }
+
+    builder.build();
+  }
+
+  *world.write_resource::<SpawnStats>() = state.spawn_stats.clone();
+}
@@ -35,6 +35,18 @@ mod constant_velocity;
 mod drop_ram;
 mod level;
 mod exodus;
+mod level_outcome;
+mod endless;
+mod ghost;
+mod rewind;
+mod level_state;
+mod time_scale;
+mod config_watcher;
+mod music;
+mod sound_dispatcher;
+mod sprite_animation;
+mod creep_animation;
+mod camera_projection;
 
 pub use self::basic_velocity::*;
 pub use self::bundle::*;
@@ -61,6 +73,18 @@ pub use self::constant_velocity::*;
 pub use self::drop_ram::*;
 pub use self::level::*;
 pub use self::exodus::*;
+pub use self::level_outcome::*;
+pub use self::endless::*;
+pub use self::ghost::*;
+pub use self::rewind::*;
+pub use self::level_state::*;
+pub use self::time_scale::*;
+pub use self::config_watcher::*;
+pub use self::music::*;
+pub use self::sound_dispatcher::*;
+pub use self::sprite_animation::*;
+pub use self::creep_animation::*;
+pub use self::camera_projection::*;
 
 //Not exactly sure how to structure this
 //Want the function in systems so things like TransformBundle dependencies on my systems
@@ -0,0 +1,48 @@
+use amethyst::{
+  audio::{
+    output::Output,
+    AudioSink,
+  },
+  ecs::prelude::*,
+};
+
+use ::resources::{
+  Level,
+  LoadState,
+  Sounds,
+};
+
+///Keeps the music volume in step with the settings and switches playlist when a level with its own music loads.
+///The tracks themselves are queued by the DJ system through Sounds::next_music.
+#[derive(Default)]
+pub struct Music;
+
+impl<'s> System<'s> for Music {
+  type SystemData = (
+    Read<'s, Level>,
+    WriteExpect<'s, Sounds>,
+    Option<Read<'s, Output>>,
+    Option<Write<'s, AudioSink>>,
+  );
+
+  fn run(&mut self, (level, mut sounds, output, sink): Self::SystemData) {
+    let mut sink = match sink {
+      Some(sink) => sink,
+      //No audio device
+      None => return,
+    };
+
+    //Only switch once the level's loaded, otherwise restarting a level would restart the music too
+    if level.load_state == LoadState::Loaded && sounds.set_playlist(level.config().music.as_ref()) {
+      if let Some(output) = output {
+        //Dropping the old sink stops whatever was playing, the DJ fills the new one from the new playlist
+        *sink = AudioSink::new(&output);
+      }
+    }
+
+    let volume = sounds.music_volume();
+    if sink.volume() != volume {
+      sink.set_volume(volume);
+    }
+  }
+}
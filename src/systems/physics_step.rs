@@ -10,7 +10,10 @@ use amethyst::{
 
 use ::{
   components::Collider,
-  resources::PhysicsWorld,
+  resources::{
+    PhysicsWorld,
+    Simulation,
+  },
 };
 
 ///System steps the physics world. Also manages deleting colliders when their components get removed.
@@ -27,6 +30,7 @@ impl<'s> System<'s> for PhysicsStep {
   type SystemData = (
     Entities<'s>,
     Read<'s, Time>,
+    Read<'s, Simulation>,
     Write<'s, PhysicsWorld>,
     WriteStorage<'s, Collider>,
   );
@@ -38,8 +42,8 @@ impl<'s> System<'s> for PhysicsStep {
     self.removed_reader_id = Some(storage.track_removed());
   }
 
-  fn run(&mut self, (entities, time, mut physics_world, mut colliders): Self::SystemData) {
-    let delta = time.delta_seconds();
+  fn run(&mut self, (entities, time, simulation, mut physics_world, mut colliders): Self::SystemData) {
+    let delta = simulation.delta(&time);
     physics_world.add_time(delta);
 
     while physics_world.step() {
@@ -6,9 +6,11 @@ use amethyst::{
 };
 
 use ::resources::{
+  AudioSettings,
   Command,
   CommandChannel,
   Sounds,
+  Simulation,
   also_kills,
 };
 
@@ -16,6 +18,8 @@ use ::resources::{
 #[derive(Default)]
 pub struct PlayerInput {
   down_actions: HashSet<String>,
+  //Volume is saved once the key is let go rather than every frame it's held
+  volume_changed: bool,
 }
 
 impl<'s> System<'s> for PlayerInput {
@@ -23,9 +27,11 @@ impl<'s> System<'s> for PlayerInput {
     Read<'s, InputHandler<String, String>>,
     Write<'s, CommandChannel>,
     WriteExpect<'s, Sounds>,
+    Write<'s, AudioSettings>,
+    Write<'s, Simulation>,
   );
 
-  fn run(&mut self, (input, mut commands, mut sounds): Self::SystemData) {
+  fn run(&mut self, (input, mut commands, mut sounds, mut audio_settings, mut simulation): Self::SystemData) {
     for action in input.bindings.actions() {
       let was_down = self.down_actions.contains(&action);
       let is_down = input.action_is_down(&action).unwrap_or(false);
@@ -36,6 +42,13 @@ impl<'s> System<'s> for PlayerInput {
       if released {
         self.down_actions.remove(&action);
       } else if pressed {
+        self.down_actions.insert(action.clone());
+
+        //Keep tracking what's held while paused but don't let anything through to the game
+        if simulation.paused {
+          continue;
+        }
+
         let cmd = match action.as_ref() {
           "drop_cube" => Some(Command::DropCube),
           "drop_lift" => Some(Command::DropLift),
@@ -46,6 +59,12 @@ impl<'s> System<'s> for PlayerInput {
           "restart_level" => Some(Command::RestartLevel),
           "ram" => Some(Command::DropRam),
           "exodus" => Some(Command::Exodus),
+          "save_state" => Some(Command::SaveLevelState),
+          "load_state" => Some(Command::LoadLevelState),
+          "slow_down" => Some(Command::SlowDown),
+          "speed_up" => Some(Command::SpeedUp),
+          "toggle_projection" => Some(Command::ToggleProjection),
+          "rewind" => None,
           o => {
             debug!("Unhandled input action: {:?}", o);
             None
@@ -58,22 +77,34 @@ impl<'s> System<'s> for PlayerInput {
             commands.single_write(Command::KillMatriarch);
           }
         }
-        self.down_actions.insert(action);
       }
     }
 
+    //Rewind works for as long as it's held rather than on press
+    simulation.rewinding = !simulation.paused && self.down_actions.contains("rewind");
+
+    let mut volume_changing = false;
     for axis in input.bindings.axes() {
       let value = input.axis_value(&axis).unwrap_or(0.0);
       if value != 0.0 {
         match axis.as_ref() {
-          "move_z" => commands.single_write(Command::Zoom(value as f32)),
+          "move_z" => if !simulation.paused {
+            commands.single_write(Command::Zoom(value as f32))
+          },
           "volume" => {
             let v = sounds.volume + 0.01 * value as f32;
             sounds.volume = v.min(1.0).max(0.0);
+            audio_settings.volume = Some(sounds.volume);
+            volume_changing = true;
           },
           o => debug!("Unhandled input axis {} value: {}", o, value),
         }
       }
     }
+
+    if self.volume_changed && !volume_changing {
+      audio_settings.save();
+    }
+    self.volume_changed = volume_changing;
   }
 }
\ No newline at end of file
@@ -0,0 +1,277 @@
+use std::collections::HashSet;
+
+use amethyst::{
+  core::{
+    cgmath::Vector2,
+    transform::components::Transform,
+  },
+  ecs::prelude::*,
+};
+
+use ::{
+  components::{
+    Age,
+    ChangeDirection,
+    Collider,
+    Color,
+    ConstantVelocity,
+    Family,
+    LaunchArea,
+    Matriarch,
+    Shape,
+    Spawner,
+    Walker,
+  },
+  resources::{
+    CreepSnapshot,
+    GameMode,
+    GhostRun,
+    Level,
+    LoadState,
+    PhysicsWorld,
+    RewindBuffer,
+    Simulation,
+    Snapshot,
+    SpawnerSnapshot,
+    SpawnStats,
+    REWIND_SNAPSHOT_STEPS,
+  },
+  systems::creep_collision_groups,
+};
+
+//Restore a snapshot every n frames while rewind is held. Snapshots are 0.1s apart so this rewinds at ~3x
+const REWIND_FRAMES: u32 = 2;
+
+///Snapshots the gameplay state every few physics steps and restores them while rewind is held
+#[derive(Default)]
+pub struct Rewind {
+  frames_held: u32,
+}
+
+impl<'s> System<'s> for Rewind {
+  type SystemData = (
+    Entities<'s>,
+    ReadStorage<'s, Collider>,
+    ReadStorage<'s, Walker>,
+    ReadStorage<'s, Age>,
+    ReadStorage<'s, Matriarch>,
+    ReadStorage<'s, Color>,
+    ReadStorage<'s, Spawner>,
+    ReadStorage<'s, Shape>,
+    ReadStorage<'s, Transform>,
+    ReadStorage<'s, LaunchArea>,
+    ReadStorage<'s, ChangeDirection>,
+    ReadStorage<'s, ConstantVelocity>,
+    Read<'s, PhysicsWorld>,
+    Read<'s, SpawnStats>,
+    Read<'s, Level>,
+    Read<'s, Simulation>,
+    Read<'s, GhostRun>,
+    Write<'s, RewindBuffer>,
+    Read<'s, LazyUpdate>,
+  );
+
+  fn run(&mut self, (entities, colliders, walkers, ages, matriarchs, colors, spawners, shapes, transforms, launch_areas, change_directions, constant_velocities, physics_world, spawn_stats, level, simulation, ghost_run, mut rewind_buffer, updater): Self::SystemData) {
+    let endless = match level.mode {
+      GameMode::Endless(_) => true,
+      _ => false,
+    };
+    //Endless terrain isn't part of the snapshot so it can't be rewound
+    if level.load_state != LoadState::Loaded || level.outcome.is_some() || endless {
+      self.frames_held = 0;
+      return;
+    }
+
+    if simulation.rewinding {
+      if self.frames_held % REWIND_FRAMES == 0 {
+        if let Some(snapshot) = rewind_buffer.snapshots.pop_back() {
+          updater.exec_mut(move |world| restore(world, snapshot));
+        }
+      }
+      self.frames_held += 1;
+      return;
+    }
+    self.frames_held = 0;
+
+    let step = physics_world.step_count();
+    if rewind_buffer.last_step().map_or(false, |last| step < last + REWIND_SNAPSHOT_STEPS) {
+      return;
+    }
+
+    let mut creeps = Vec::new();
+    for (entity, collider, walker, age) in (&*entities, &colliders, &walkers, &ages).join() {
+      if let Some((position, velocity)) = physics_world.body_state(collider.body_handle) {
+        creeps.push(CreepSnapshot {
+          entity,
+          position,
+          velocity,
+          walker: walker.clone(),
+          age: *age,
+          matriarch: matriarchs.get(entity).cloned(),
+          color: colors.get(entity).cloned(),
+        });
+      }
+    }
+
+    let mut spawner_snapshots = Vec::new();
+    for (entity, spawner, shape, transform) in (&*entities, &spawners, &shapes, &transforms).join() {
+      spawner_snapshots.push(SpawnerSnapshot {
+        entity,
+        spawner: spawner.clone(),
+        position: Vector2::new(transform.translation.x, transform.translation.y),
+        //Shape scale is half extents
+        size: Vector2::new(shape.scale.0 * 2.0, shape.scale.1 * 2.0),
+        color: colors.get(entity).cloned(),
+      });
+    }
+
+    let abilities = (&*entities, &launch_areas).join().map(|(e, _)| e)
+      .chain((&*entities, &change_directions).join().map(|(e, _)| e))
+      .chain((&*entities, &constant_velocities).join().map(|(e, _)| e))
+      .collect::<HashSet<_>>();
+
+    rewind_buffer.push(Snapshot {
+      step,
+      runtime: level.runtime,
+      ghost_points: ghost_run.recording.points.len(),
+      spawn_stats: spawn_stats.clone(),
+      creeps,
+      spawners: spawner_snapshots,
+      abilities,
+    });
+  }
+}
+
+//Puts the world back the way it was when the snapshot was taken
+fn restore(world: &mut World, snapshot: Snapshot) {
+  debug!("Rewinding to step {}", snapshot.step);
+
+  *world.write_resource::<SpawnStats>() = snapshot.spawn_stats.clone();
+  world.write_resource::<Level>().runtime = snapshot.runtime;
+  //TimeAttack::run_time is left alone, the run's clock keeps going while rewinding (see the Level system)
+
+  //Rewind the ghost recording too so the track stays in step with the level
+  {
+    let step = world.read_resource::<PhysicsWorld>().step_count();
+    let mut ghost_run = world.write_resource::<GhostRun>();
+    if ghost_run.start_step.is_some() {
+      ghost_run.recording.points.truncate(snapshot.ghost_points);
+      ghost_run.start_step = Some(step - snapshot.ghost_points as u64);
+    }
+  }
+
+  //Anything that's appeared since goes
+  {
+    let entities = world.entities();
+    let snapshot_creeps: HashSet<Entity> = snapshot.creeps.iter().map(|c| c.entity).collect();
+    for (entity, _) in (&*entities, &world.read_storage::<Walker>()).join() {
+      if !snapshot_creeps.contains(&entity) {
+        entities
+          .delete(entity)
+          .expect("Failed to delete entity");
+      }
+    }
+
+    let abilities = (&*entities, &world.read_storage::<LaunchArea>()).join().map(|(e, _)| e)
+      .chain((&*entities, &world.read_storage::<ChangeDirection>()).join().map(|(e, _)| e))
+      .chain((&*entities, &world.read_storage::<ConstantVelocity>()).join().map(|(e, _)| e))
+      .collect::<Vec<_>>();
+    for entity in abilities {
+      if !snapshot.abilities.contains(&entity) {
+        entities
+          .delete(entity)
+          .expect("Failed to delete entity");
+      }
+    }
+  }
+
+  //The matriarch might not be the same creep any more
+  {
+    let entities = world.entities();
+    let mut matriarchs = world.write_storage::<Matriarch>();
+    let current = (&*entities, &matriarchs).join().map(|(e, _)| e).collect::<Vec<_>>();
+    for entity in current {
+      matriarchs.remove(entity);
+    }
+  }
+
+  for creep in &snapshot.creeps {
+    let alive = world.entities().is_alive(creep.entity) && world.read_storage::<Walker>().contains(creep.entity);
+    let entity = if alive {
+      let mut colliders = world.write_storage::<Collider>();
+      if let Some(collider) = colliders.get_mut(creep.entity) {
+        world
+          .write_resource::<PhysicsWorld>()
+          .set_body_state(collider.body_handle, &creep.position, &creep.velocity);
+        //Twice so there's nothing to lerp between
+        collider.update_transform(&creep.position);
+        collider.update_transform(&creep.position);
+      }
+      world.write_storage::<Walker>().insert(creep.entity, creep.walker.clone()).expect("Failed to restore Walker");
+      world.write_storage::<Age>().insert(creep.entity, creep.age).expect("Failed to restore Age");
+      creep.entity
+    } else {
+      let entity = recreate_creep(world, creep);
+      world.write_resource::<RewindBuffer>().replace_entity(creep.entity, entity);
+      entity
+    };
+
+    if let Some(ref matriarch) = creep.matriarch {
+      world
+        .write_storage::<Matriarch>()
+        .insert(entity, matriarch.clone())
+        .expect("Failed to restore Matriarch");
+    }
+  }
+
+  for spawner in &snapshot.spawners {
+    let alive = world.entities().is_alive(spawner.entity) && world.read_storage::<Spawner>().contains(spawner.entity);
+    if alive {
+      world
+        .write_storage::<Spawner>()
+        .insert(spawner.entity, spawner.spawner.clone())
+        .expect("Failed to restore Spawner");
+    } else {
+      let collider = world
+        .write_resource::<PhysicsWorld>()
+        .create_ground_box_sensor(&spawner.position, &spawner.size, 0.0);
+      let mut builder = world
+        .create_entity()
+        .with(collider)
+        .with(spawner.spawner.clone());
+      if let Some(color) = spawner.color {
+        builder = builder.with(color);
+      }
+      let entity = builder.build();
+      world.write_resource::<RewindBuffer>().replace_entity(spawner.entity, entity);
+    }
+  }
+}
+
+//Same as the Spawner system does but at the snapshot's position and velocity
+fn recreate_creep(world: &mut World, creep: &CreepSnapshot) -> Entity {
+  let collider = {
+    let mut physics_world = world.write_resource::<PhysicsWorld>();
+    let collider = physics_world.create_rigid_body_with_box_collider(
+      &Vector2::new(0.0, 0.0),
+      &Vector2::new(10.0, 10.0),
+      0.0);
+    physics_world
+      .world
+      .collision_world_mut()
+      .set_collision_groups(collider.collider_handle, creep_collision_groups());
+    physics_world.set_body_state(collider.body_handle, &creep.position, &creep.velocity);
+    collider
+  };
+
+  let mut builder = world
+    .create_entity()
+    .with(collider)
+    .with(Family::default())
+    .with(creep.age)
+    .with(creep.walker.clone());
+  if let Some(color) = creep.color {
+    builder = builder.with(color);
+  }
+  builder.build()
+}
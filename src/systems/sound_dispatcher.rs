@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use amethyst::{
+  assets::AssetStorage,
+  audio::Source,
+  controls::FlyControlTag,
+  core::{
+    cgmath::{
+      InnerSpace,
+      Vector2,
+      Vector3,
+    },
+    transform::components::Transform,
+    Time,
+  },
+  ecs::prelude::*,
+  shrev::ReaderId,
+};
+
+use ::{
+  config::{
+    CameraConfig,
+    PositionalConfig,
+    SoundConfig,
+  },
+  resources::{
+    SoundChannel,
+    SoundEvent,
+    Sounds,
+  },
+};
+
+//Each extra copy of a sound in the same frame makes the single cue that's played this much louder...
+const AGGREGATE_BOOST: f32 = 0.15;
+//...up to this much
+const MAX_AGGREGATE_BOOST: f32 = 2.0;
+
+//All the copies of one sound sent this frame
+#[derive(Default)]
+struct Cue {
+  count: u32,
+  //The loudest copy sets the volume, the pan is the average weighted by volume
+  attenuation: f32,
+  weighted_pan: f32,
+  total_attenuation: f32,
+}
+
+///Plays everything sent to the SoundChannel. Multiple of the same sound in a frame are played once but louder,
+///Sounds takes care of cooldowns and the voice limit.
+#[derive(Default)]
+pub struct SoundDispatcher {
+  sound_reader: Option<ReaderId<SoundEvent>>,
+}
+
+//Pan (-1 to 1) and volume for a sound at position heard from the camera
+fn spatialize(position: Vector2<f32>, camera: Vector3<f32>, scale: f32, config: &PositionalConfig) -> (f32, f32) {
+  let offset = position - Vector2::new(camera.x, camera.y);
+  let pan = if config.pan_distance > 0.0 {
+    (offset.x / (config.pan_distance * scale)).max(-1.0).min(1.0)
+  } else {
+    0.0
+  };
+  let attenuation = if config.falloff_distance > 0.0 {
+    let falloff = (offset.magnitude() / (config.falloff_distance * scale)).min(1.0);
+    1.0 - falloff * (1.0 - config.min_volume)
+  } else {
+    1.0
+  };
+  (pan, attenuation)
+}
+
+impl<'s> System<'s> for SoundDispatcher {
+  type SystemData = (
+    Read<'s, SoundChannel>,
+    Read<'s, Time>,
+    ReadStorage<'s, Transform>,
+    ReadStorage<'s, FlyControlTag>,
+    Read<'s, SoundConfig>,
+    Read<'s, CameraConfig>,
+    WriteExpect<'s, Sounds>,
+    Read<'s, AssetStorage<Source>>,
+  );
+
+  fn setup(&mut self, res: &mut Resources) {
+    Self::SystemData::setup(res);
+    self.sound_reader = Some(res.fetch_mut::<SoundChannel>().register_reader());
+  }
+
+  fn run(&mut self, (sound_events, time, transforms, fly_tags, sound_config, camera_config, mut sounds, source_storage): Self::SystemData) {
+    let camera = (&transforms, &fly_tags)
+      .join()
+      .next()
+      .map(|(t, _)| t.translation);
+    //Zooming out brings more of the level into earshot
+    let scale = match camera {
+      Some(camera) if camera_config.z_default > 0.0 => camera.z.max(camera_config.z_min) / camera_config.z_default,
+      _ => 1.0,
+    };
+
+    let mut cues: HashMap<&str, Cue> = HashMap::new();
+    for event in sound_events.read(self.sound_reader.as_mut().unwrap()) {
+      let (pan, attenuation) = match (event.position, camera) {
+        (Some(position), Some(camera)) => spatialize(position, camera, scale, &sound_config.positional),
+        _ => (0.0, 1.0),
+      };
+
+      let cue = cues.entry(event.id.as_str()).or_insert_with(Cue::default);
+      cue.count += 1;
+      cue.attenuation = cue.attenuation.max(attenuation);
+      cue.weighted_pan += pan * attenuation;
+      cue.total_attenuation += attenuation;
+    }
+
+    //Most important first so they get the voices if there aren't enough to go round
+    let mut cues: Vec<(&str, Cue)> = cues.into_iter().collect();
+    cues.sort_by_key(|(id, _)| ::std::cmp::Reverse(sounds.priority(id)));
+
+    let now = time.absolute_real_time_seconds();
+    for (id, cue) in cues {
+      let boost = (1.0 + AGGREGATE_BOOST * (cue.count - 1) as f32).min(MAX_AGGREGATE_BOOST);
+      let pan = if cue.total_attenuation > 0.0 { cue.weighted_pan / cue.total_attenuation } else { 0.0 };
+      sounds.play(id, boost * cue.attenuation, pan, now, &source_storage);
+    }
+  }
+}
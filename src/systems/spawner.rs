@@ -5,11 +5,6 @@ use amethyst::{
     cgmath::Vector2,
   },
   ecs::prelude::*,
-  assets::AssetStorage,
-  audio::{
-    Source,
-    output::Output,
-  },
 };
 
 use ncollide2d::world::CollisionGroups;
@@ -25,7 +20,9 @@ use ::{
   resources::{
     PhysicsWorld,
     SpawnStats,
-    Sounds,
+    SoundChannel,
+    SoundEvent,
+    Simulation,
   },
 };
 
@@ -36,40 +33,47 @@ pub struct Spawner {
 
 impl Default for Spawner {
   fn default() -> Self {
-    let mut collision_groups = CollisionGroups::new();
-    collision_groups.set_membership(&[1]);
-
-    //The whitelist is usually 1-29, we don't want to collide with ourselves so take 1 out
-    let mut wl = Vec::new();
-    wl.extend(2..=29);
-    collision_groups.set_whitelist(&wl);
-
     Self {
-      collision_groups,
+      collision_groups: creep_collision_groups(),
     }
   }
 }
 
+///Collision groups spawned creeps are put in. Anything else that creates creeps should use the same ones.
+pub fn creep_collision_groups() -> CollisionGroups {
+  let mut collision_groups = CollisionGroups::new();
+  collision_groups.set_membership(&[1]);
+
+  //The whitelist is usually 1-29, we don't want to collide with ourselves so take 1 out
+  let mut wl = Vec::new();
+  wl.extend(2..=29);
+  collision_groups.set_whitelist(&wl);
+
+  collision_groups
+}
+
 impl<'s> System<'s> for Spawner {
   type SystemData = (
     Entities<'s>,
     ReadStorage<'s, Transform>,
     Read<'s, Time>,
+    Read<'s, Simulation>,
     WriteStorage<'s, SpawnerComponent>,
     Write<'s, SpawnStats>,
     Write<'s, PhysicsWorld>,
-    ReadExpect<'s, Sounds>,
-    Read<'s, AssetStorage<Source>>,
-    Option<Read<'s, Output>>,
+    Write<'s, SoundChannel>,
     Read<'s, LazyUpdate>,
     Read<'s, SpawnerConfig>,
   );
 
-  fn run(&mut self, (entities, transforms, time, mut spawners, mut spawn_stats, mut physics_world, sounds, source_storage, output, updater, spawner_config): Self::SystemData) {
-    let delta = time.delta_seconds();
+  fn run(&mut self, (entities, transforms, time, simulation, mut spawners, mut spawn_stats, mut physics_world, mut sound_events, updater, spawner_config): Self::SystemData) {
+    let delta = simulation.delta(&time);
 
     //Increase elapsed time for all Spawners
     for (e, s, t) in (&entities, &mut spawners, &transforms).join() {
+      if s.dormant {
+        continue;
+      }
       s.elapsed += delta;
 
       if s.exodus {
@@ -106,9 +110,7 @@ impl<'s> System<'s> for Spawner {
 
         debug!("Spawner ({:?}) spawned: {:?}", e, new);
 
-        if let Some(output) = &output {
-          sounds.play_spawn(&source_storage, output);
-        }
+        sound_events.single_write(SoundEvent::at("spawn", Vector2::new(t.translation.x, t.translation.y)));
       }
 
       if s.spawn_count >= s.spawn_max {
@@ -0,0 +1,43 @@
+use amethyst::{
+  core::Time,
+  ecs::prelude::*,
+  renderer::SpriteRender,
+};
+
+use ::{
+  components::SpriteAnimation as SpriteAnimationComponent,
+  resources::{
+    Simulation,
+    Sprites,
+  },
+};
+
+///Moves every SpriteAnimation on and sets the sprite its SpriteRender shows. Runs on simulation time so
+///pausing or slowing the game does the same to the animations.
+#[derive(Default)]
+pub struct SpriteAnimation;
+
+impl<'s> System<'s> for SpriteAnimation {
+  type SystemData = (
+    Read<'s, Time>,
+    Read<'s, Simulation>,
+    ReadExpect<'s, Sprites>,
+    WriteStorage<'s, SpriteAnimationComponent>,
+    WriteStorage<'s, SpriteRender>,
+  );
+
+  fn run(&mut self, (time, simulation, sprites, mut animations, mut renders): Self::SystemData) {
+    let delta = simulation.delta(&time);
+
+    for (animation, render) in (&mut animations, &mut renders).join() {
+      animation.elapsed += delta;
+      if let Some(a) = sprites.animation(&animation.animation) {
+        let (sprite_sheet, sprite_number) = a.frame_at(animation.elapsed);
+        if render.sprite_number != sprite_number || render.sprite_sheet != *sprite_sheet {
+          render.sprite_number = sprite_number;
+          render.sprite_sheet = sprite_sheet.clone();
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,68 @@
+use std::cmp::Ordering;
+
+use amethyst::{
+  ecs::prelude::*,
+  shrev::ReaderId,
+};
+
+use ::{
+  config::PhysicsConfig,
+  resources::{
+    Command,
+    CommandChannel,
+    Simulation,
+  },
+};
+
+///Steps the simulation speed through the configured time scales
+#[derive(Default)]
+pub struct TimeScale {
+  command_reader: Option<ReaderId<Command>>,
+}
+
+impl<'s> System<'s> for TimeScale {
+  type SystemData = (
+    Read<'s, CommandChannel>,
+    Read<'s, PhysicsConfig>,
+    Write<'s, Simulation>,
+  );
+
+  fn setup(&mut self, res: &mut Resources) {
+    Self::SystemData::setup(res);
+    self.command_reader = Some(res.fetch_mut::<CommandChannel>().register_reader());
+  }
+
+  fn run(&mut self, (commands, physics_config, mut simulation): Self::SystemData) {
+    for command in commands.read(self.command_reader.as_mut().unwrap()) {
+      let step = match command {
+        Command::SlowDown => -1,
+        Command::SpeedUp => 1,
+        _ => continue,
+      };
+
+      //A NaN or infinite scale would poison the closest-scale search below and 0 or less would stop or reverse time.
+      //Stepping up and down needs them in order too
+      let mut scales: Vec<f32> = physics_config.time_scales
+        .iter()
+        .cloned()
+        .filter(|scale| scale.is_finite() && *scale > 0.0)
+        .collect();
+      scales.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+      if scales.is_empty() {
+        continue;
+      }
+
+      //Start from whichever configured scale is closest in case the current one isn't in the list
+      let current = scales
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| (*a - simulation.time_scale).abs().partial_cmp(&(*b - simulation.time_scale).abs()).unwrap_or(Ordering::Equal))
+        .map(|(i, _)| i as i32)
+        .unwrap_or(0);
+      let index = (current + step).max(0).min(scales.len() as i32 - 1) as usize;
+
+      simulation.time_scale = scales[index];
+      info!("Time scale {}x", simulation.time_scale);
+    }
+  }
+}